@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 use aurand::msg::{ExecuteMsg, QueryMsg};
-use aurand::msg::InstantiateMsg;
+use aurand::msg::{InstantiateMsg, MigrateMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
-        query: QueryMsg
+        query: QueryMsg,
+        migrate: MigrateMsg
     }
 }
@@ -2,42 +2,67 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_binary, Binary, Deps, DepsMut, Env, Addr, Api, Timestamp, SubMsg, coins, Uint128,
-    MessageInfo, ReplyOn, Response, StdResult, WasmMsg, ensure_eq, Order, BankMsg, Reply
+    MessageInfo, ReplyOn, Response, StdResult, WasmMsg, ensure_eq, Order, BankMsg, Reply, Event,
+    Storage, HexBinary, SubMsgResult, Decimal, StdError,
 };
-use cw2::set_contract_version;
+use cw2::{set_contract_version, get_contract_version};
+use cw_storage_plus::Bound;
 
 use nois::{NoisCallback, ProxyExecuteMsg};
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, CallbackExecuteMsg,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, CallbackExecuteMsg,
     PendingCommitmentsQuery, CommitmentsQuery, BotInfoQuery,
-    NumberOfCommitmentQuery, ConfigsQuery
+    NumberOfCommitmentQuery, ConfigsQuery, CommitmentQuery, RequestPriceQuery, OwnershipQuery,
+    RoleMembersQuery, ScheduledConfigChangesQuery, TreasuryQuery,
+    BotRewardsQuery, BotsQuery, VerificationKeysQuery,
 };
 use crate::state::{
-    CONFIGS, Configs, NOIS_CONFIGS, NoisConfigs, TIME_CONFIGS, TimeConfigs,
-    COMMITMENTS, PENDING_COMMITMENTS, Commitment, DataRequest, get_commitments, get_commitment,
-    BOTS, Bot,
-    OWNER, NONCES,
+    CONFIGS, Configs, NOIS_CONFIGS, NoisConfigs, TIME_CONFIGS, TimeConfigs, GAS_CONFIGS, GasConfigs,
+    STAKING_CONFIGS, StakingConfigs, BOT_STAKES, Unbonding, slash_stake,
+    BotStatus, REGISTRATION_MODE, RegistrationMode, BOT_METRICS, record_submission,
+    ApiKey, API_KEY_POLICY, ApiKeyPolicy, BOT_TOMBSTONES, API_KEY_TOMBSTONES, API_KEY_OWNERS, tombstone_bot,
+    COMMITMENTS, PENDING_COMMITMENTS, Commitment, DataRequest, add_commitment, get_commitments, get_commitment,
+    get_oldest_pending_commitment, settle_commitment, COMMITMENT_STATUS, CommitmentStatus,
+    COMMITMENT_RECORDS, REQUEST_COMMITMENTS,
+    DerivedRandomness, CallbackDelivery, CALLBACK_DELIVERIES, CALLBACK_DELIVERY_COUNT, FailedCallback, FAILED_CALLBACKS,
+    DeliveryMode, RANDOMNESS_RESULTS,
+    BOTS, BOTS_BY_STATUS, Bot, SERIAL_NUMBERS,
+    OWNER, PENDING_OWNER, PendingOwner, NONCES,
+    ROLES, Role, has_role, PAUSE_STATUS, TREASURY, credit_protocol_fees,
+    BOT_REWARDS, credit_bot_rewards, take_bot_rewards,
+    REJECT_DEVELOPER_LICENSE, VERIFICATION_KEYS, VerificationKey, add_verification_key, get_verification_keys_at,
+    ConfigChange, ScheduledConfigChange, SCHEDULED_CONFIG_CHANGES, CONFIG_CHANGE_COUNT, CONFIG_CHANGE_DELAY,
 };
-use crate::rsa_verify::{verify_message};
+use crate::rsa_verify::{verify_message, public_key, RANDOM_ORG_MODULUS, RANDOM_ORG_EXPONENT};
 use crate::utils::{
     generate_hex_randomness, generate_int_randomness,
     make_commit_id, 
-    decode_randomorg_data,
-    convert_datetime_string
+    decode_randomorg_data, validate_randomorg_data,
+    convert_datetime_string,
+    parse_version,
 };
+use crate::migrations::migrate_from_v0_1_0;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:aurand";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const NOIS_CALLBACK_REPLY_ID: u64 = 1;
-const COMMITMENT_CALLBACK_REPLY_ID: u64 = 2;
+const EXPIRED_CALLBACK_REPLY_ID: u64 = 3;
+const COMMITMENT_CALLBACK_REPLY_ID_START: u64 = 1000; // each commitment callback takes its own reply id from here up
 
 const HEX_DATA_TYPE: &str = "hex";
 const INT_DATA_TYPE: &str = "int";
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub const MAX_FEE_BPS: u16 = 10000;
+
+const CONFIG_CHANGE_EXECUTION_WINDOW: u64 = 604800; // time a due config change can be executed (seconds), 7 days
+
 const MIN_NUM: u32 = 1;
 const MAX_NUM: u32 = 256;
 
@@ -56,11 +81,24 @@ pub fn instantiate(
         .addr_validate(&_msg.nois_proxy)
         .map_err(|_| ContractError::InvalidProxyAddress{})?;
 
+    if _msg.protocol_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFeeSplit{});
+    }
+
+    // bot must be able to register with at least one api key
+    if _msg.max_api_keys == 0 {
+        return Err(ContractError::InvalidApiKeyPolicy{});
+    }
+
+    assert_callback_gas(_msg.callback_limit_gas, _msg.max_callback_gas)?;
+
     CONFIGS.save(deps.storage, &Configs{
         bounty_denom: _msg.bounty_denom.clone(),
         fee: _msg.fee,
         callback_limit_gas: _msg.callback_limit_gas,
         max_callback: _msg.max_callback,
+        keeper_reward: _msg.keeper_reward,
+        protocol_fee_bps: _msg.protocol_fee_bps,
     })?;
 
     TIME_CONFIGS.save(deps.storage, &TimeConfigs { 
@@ -73,6 +111,37 @@ pub fn instantiate(
         nois_fee: _msg.nois_fee 
     })?;
 
+    GAS_CONFIGS.save(deps.storage, &GasConfigs { 
+        max_callback_gas: _msg.max_callback_gas, 
+        callback_gas_price: _msg.callback_gas_price, 
+    })?;
+
+    STAKING_CONFIGS.save(deps.storage, &StakingConfigs {
+        min_stake: _msg.min_stake,
+        unbonding_period: _msg.unbonding_period,
+    })?;
+
+    API_KEY_POLICY.save(deps.storage, &ApiKeyPolicy {
+        update_cooldown: _msg.api_key_update_cooldown,
+        grace_period: _msg.api_key_grace_period,
+        max_api_keys: _msg.max_api_keys,
+    })?;
+
+    CONFIG_CHANGE_DELAY.save(deps.storage, &_msg.config_change_delay)?;
+
+    REGISTRATION_MODE.save(deps.storage, &_msg.registration_mode)?;
+
+    REJECT_DEVELOPER_LICENSE.save(deps.storage, &_msg.reject_developer_license)?;
+
+    // current random org certificate verifies payloads until owner rotates it
+    add_verification_key(
+        deps.storage,
+        HexBinary::from_hex(RANDOM_ORG_MODULUS)?,
+        HexBinary::from_hex(RANDOM_ORG_EXPONENT)?,
+        Timestamp::from_seconds(0),
+        None,
+    )?;
+
     OWNER.save(deps.storage, &info.sender)?;
 
     Ok(Response::new()
@@ -80,13 +149,62 @@ pub fn instantiate(
         .add_attribute("bounty_denom", _msg.bounty_denom)
         .add_attribute("fee", _msg.fee)
         .add_attribute("callback_limit_gas", _msg.callback_limit_gas.to_string())
+        .add_attribute("keeper_reward", _msg.keeper_reward)
+        .add_attribute("protocol_fee_bps", _msg.protocol_fee_bps.to_string())
         .add_attribute("time_expired", _msg.time_expired.to_string())
         .add_attribute("time_per_block", _msg.time_per_block.to_string())
         .add_attribute("nois_proxy", nois_proxy_addr.to_string())
         .add_attribute("nois_fee", _msg.nois_fee)
+        .add_attribute("max_callback_gas", _msg.max_callback_gas.to_string())
+        .add_attribute("callback_gas_price", _msg.callback_gas_price.to_string())
+        .add_attribute("config_change_delay", _msg.config_change_delay.to_string())
+        .add_attribute("min_stake", _msg.min_stake)
+        .add_attribute("unbonding_period", _msg.unbonding_period.to_string())
+        .add_attribute("registration_mode", registration_mode_str(&_msg.registration_mode))
+        .add_attribute("api_key_update_cooldown", _msg.api_key_update_cooldown.to_string())
+        .add_attribute("api_key_grace_period", _msg.api_key_grace_period.to_string())
+        .add_attribute("max_api_keys", _msg.max_api_keys.to_string())
+        .add_attribute("reject_developer_license", _msg.reject_developer_license.to_string())
         .add_attribute("owner", info.sender))
 }
 
+/// Handling contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName{});
+    }
+
+    let stored_version = parse_version(&stored.version).ok_or(ContractError::InvalidContractVersion{})?;
+    let new_version = parse_version(CONTRACT_VERSION).ok_or(ContractError::InvalidContractVersion{})?;
+
+    // refuse downgrade, state of newer version can not be read by older code
+    if stored_version > new_version {
+        return Err(ContractError::InvalidContractVersion{});
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION);
+
+    // run state transformations of every version after stored one
+    if stored_version < (0, 2, 0) {
+        let migrated_commitments = migrate_from_v0_1_0(deps.storage, &_msg)?;
+        res = res.add_attribute("migrated_commitments", migrated_commitments.to_string());
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(res)
+}
+
 /// Handling contract execution
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
@@ -101,7 +219,51 @@ pub fn execute(
             fee,
             callback_limit_gas,
             max_callback,
-        } => execute_set_configs(_deps,_info,bounty_denom,fee,callback_limit_gas,max_callback),
+            keeper_reward,
+            protocol_fee_bps,
+        } => execute_set_configs(_deps,_env,_info,Configs{
+            bounty_denom,
+            fee,
+            callback_limit_gas,
+            max_callback,
+            keeper_reward,
+            protocol_fee_bps,
+        }),
+
+        ExecuteMsg::SetGasConfigs{
+            max_callback_gas,
+            callback_gas_price,
+        } => execute_set_gas_configs(_deps, _env, _info, max_callback_gas, callback_gas_price),
+
+        ExecuteMsg::SetStakingConfigs{
+            min_stake,
+            unbonding_period,
+        } => execute_set_staking_configs(_deps, _env, _info, StakingConfigs{
+            min_stake,
+            unbonding_period,
+        }),
+
+        ExecuteMsg::SetApiKeyPolicy{
+            update_cooldown,
+            grace_period,
+            max_api_keys,
+        } => execute_set_api_key_policy(_deps, _env, _info, ApiKeyPolicy{
+            update_cooldown,
+            grace_period,
+            max_api_keys,
+        }),
+
+        ExecuteMsg::SetConfigChangeDelay{
+            delay,
+        } => execute_set_config_change_delay(_deps, _env, _info, delay),
+
+        ExecuteMsg::ExecuteConfigChange{
+            change_id,
+        } => execute_execute_config_change(_deps, _env, _info, change_id),
+
+        ExecuteMsg::CancelConfigChange{
+            change_id,
+        } => execute_cancel_config_change(_deps, _info, change_id),
 
         ExecuteMsg::SetTimeConfigs{
             time_expired,
@@ -115,12 +277,93 @@ pub fn execute(
             let api = _deps.api;
             execute_set_nois_configs(
                 _deps, 
+                _env,
                 _info, 
                 optional_addr_validate(api,nois_proxy)?, 
                 nois_fee
             )
         },
 
+        ExecuteMsg::ProposeOwner{
+            new_owner,
+            expiry,
+        } => {
+            let api = _deps.api;
+            execute_propose_owner(
+                _deps,
+                _env,
+                _info,
+                optional_addr_validate(api,new_owner)?,
+                expiry,
+            )
+        },
+
+        ExecuteMsg::AcceptOwnership{} => execute_accept_ownership(_deps,_env,_info),
+
+        ExecuteMsg::CancelOwnershipProposal{} => execute_cancel_ownership_proposal(_deps,_info),
+
+        ExecuteMsg::RenounceOwnership{} => execute_renounce_ownership(_deps,_info),
+
+        ExecuteMsg::GrantRole{
+            role,
+            address,
+        } => {
+            let api = _deps.api;
+            execute_grant_role(
+                _deps,
+                _info,
+                role,
+                optional_addr_validate(api,address)?,
+            )
+        },
+
+        ExecuteMsg::RevokeRole{
+            role,
+            address,
+        } => {
+            let api = _deps.api;
+            execute_revoke_role(
+                _deps,
+                _info,
+                role,
+                optional_addr_validate(api,address)?,
+            )
+        },
+
+        ExecuteMsg::AddVerificationKey{
+            modulus,
+            exponent,
+            valid_from,
+            valid_until,
+        } => execute_add_verification_key(_deps, _env, _info, modulus, exponent, valid_from, valid_until),
+
+        ExecuteMsg::SetVerificationKeyExpiry{
+            id,
+            valid_until,
+        } => execute_set_verification_key_expiry(_deps, _env, _info, id, valid_until),
+
+        ExecuteMsg::RevokeVerificationKey{
+            id,
+        } => execute_revoke_verification_key(_deps, _info, id),
+
+        ExecuteMsg::SetRejectDeveloperLicense{
+            reject,
+        } => execute_set_reject_developer_license(_deps, _info, reject),
+
+        ExecuteMsg::SetPauseStatus{
+            requests,
+            random_org,
+            nois,
+            bot_registration,
+            expiry_extension,
+        } => execute_set_pause_status(_deps,_info,PauseStatusUpdate{
+            requests,
+            random_org,
+            nois,
+            bot_registration,
+            expiry_extension,
+        }),
+
         ExecuteMsg::RegisterBot{
             hashed_api_key,
             moniker
@@ -131,37 +374,176 @@ pub fn execute(
             moniker
         } => execute_update_bot(_deps,_env,_info,hashed_api_key,moniker),
 
+        ExecuteMsg::AddApiKey{
+            hashed_api_key,
+        } => execute_add_api_key(_deps,_env,_info,hashed_api_key),
+
+        ExecuteMsg::RetireApiKey{
+            hashed_api_key,
+        } => execute_retire_api_key(_deps,_env,_info,hashed_api_key),
+
+        ExecuteMsg::DeregisterBot{
+            recipient,
+        } => {
+            let api = _deps.api;
+            execute_deregister_bot(
+                _deps,
+                _env,
+                _info,
+                recipient.map(|addr| optional_addr_validate(api, addr)).transpose()?,
+            )
+        },
+
         ExecuteMsg::RemoveBot{
             address
         } => {
             let api = _deps.api;
             execute_remove_bot(
                 _deps,
+                _env,
+                _info,
+                optional_addr_validate(api,address)?,
+            )
+        },
+
+        ExecuteMsg::SetRegistrationMode{
+            mode,
+        } => execute_set_registration_mode(_deps, _info, mode),
+
+        ExecuteMsg::ApproveBot{
+            address,
+        } => {
+            let api = _deps.api;
+            execute_approve_bot(
+                _deps,
+                _info,
+                optional_addr_validate(api,address)?,
+            )
+        },
+
+        ExecuteMsg::RejectBot{
+            address,
+        } => {
+            let api = _deps.api;
+            execute_reject_bot(
+                _deps,
+                _env,
+                _info,
+                optional_addr_validate(api,address)?,
+            )
+        },
+
+        ExecuteMsg::BondStake{} => execute_bond_stake(_deps, _info),
+
+        ExecuteMsg::UnbondStake{
+            amount,
+        } => execute_unbond_stake(_deps, _env, _info, amount),
+
+        ExecuteMsg::WithdrawStake{} => execute_withdraw_stake(_deps, _env, _info),
+
+        ExecuteMsg::SlashBot{
+            address,
+            amount,
+            reason,
+        } => {
+            let api = _deps.api;
+            execute_slash_bot(
+                _deps,
+                _env,
                 _info,
                 optional_addr_validate(api,address)?,
+                amount,
+                reason,
             )
         },
 
         ExecuteMsg::RequestHexRandomness{
             request_id, 
-            num
-        } => execute_request_hex_randomness(_deps,_env,_info,request_id,num),
+            num,
+            delivery_mode,
+            callback_address,
+            callback_payload,
+            callback_gas_limit,
+        } => {
+            let api = _deps.api;
+            execute_request_randomness(_deps,_env,_info,request_id,
+                DataRequest {data_type: HEX_DATA_TYPE.to_string(), min: 0, max: 0, num},
+                RequestOptions{
+                    delivery_mode: delivery_mode.unwrap_or(DeliveryMode::Callback),
+                    callback_address: callback_address.map(|addr| optional_addr_validate(api, addr)).transpose()?,
+                    callback_payload,
+                    callback_gas_limit,
+                }
+            )
+        },
 
         ExecuteMsg::RequestIntRandomness{
             request_id,
             min,
             max,
-            num
-        } => execute_request_int_randomness(_deps,_env,_info,request_id,min,max,num),
+            num,
+            delivery_mode,
+            callback_address,
+            callback_payload,
+            callback_gas_limit,
+        } => {
+            let api = _deps.api;
+            execute_request_randomness(_deps,_env,_info,request_id,
+                DataRequest {data_type: INT_DATA_TYPE.to_string(), min, max, num},
+                RequestOptions{
+                    delivery_mode: delivery_mode.unwrap_or(DeliveryMode::Callback),
+                    callback_address: callback_address.map(|addr| optional_addr_validate(api, addr)).transpose()?,
+                    callback_payload,
+                    callback_gas_limit,
+                }
+            )
+        },
         
         ExecuteMsg::AddRandomness{
             random_value,
             signature
-        } => execute_add_randomness(_deps,_info,random_value,signature),
+        } => execute_add_randomness(_deps,_env,_info,random_value,signature),
 
         ExecuteMsg::NoisReceive{
             callback
-        } => execute_nois_receive(_deps,_info,callback),
+        } => execute_nois_receive(_deps,_env,_info,callback),
+
+        ExecuteMsg::ClaimRewards{
+            recipient,
+        } => {
+            let api = _deps.api;
+            execute_claim_rewards(
+                _deps,
+                _info,
+                recipient.map(|addr| optional_addr_validate(api, addr)).transpose()?,
+            )
+        },
+
+        ExecuteMsg::Withdraw{
+            amount,
+            recipient,
+        } => {
+            let api = _deps.api;
+            execute_withdraw(
+                _deps,
+                _info,
+                amount,
+                optional_addr_validate(api,recipient)?,
+            )
+        },
+
+        ExecuteMsg::ReclaimExpired{
+            commitment_ids
+        } => execute_reclaim_expired(_deps,_env,_info,commitment_ids),
+
+        ExecuteMsg::RetryCallback{
+            commitment_id,
+            gas_limit
+        } => execute_retry_callback(_deps,_info,commitment_id,gas_limit),
+
+        ExecuteMsg::ClaimRandomness{
+            commitment_id
+        } => execute_claim_randomness(_deps,_info,commitment_id),
     }
 }
 
@@ -170,162 +552,1142 @@ fn optional_addr_validate(api: &dyn Api, addr: String) -> Result<Addr, ContractE
     Ok(addr)
 }
 
-fn execute_set_configs(
-    _deps: DepsMut, 
-    _info: MessageInfo, 
-    bounty_denom: String,
-    fee: Uint128,
-    callback_limit_gas: u64,
-    max_callback: u32,
-) -> Result<Response, ContractError> {
-    let owner = OWNER.load(_deps.storage)?;
+// shared guard of all owner only messages, fails when ownership has been renounced
+fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    match OWNER.may_load(storage)? {
+        Some(owner) if owner.eq(sender) => Ok(()),
+        _ => Err(ContractError::Unauthorized{}),
+    }
+}
+
+// pause flags to change, flags that are not set keep their current value
+struct PauseStatusUpdate {
+    requests: Option<bool>,
+    random_org: Option<bool>,
+    nois: Option<bool>,
+    bot_registration: Option<bool>,
+    expiry_extension: Option<u64>,
+}
 
-    if !owner.eq(&_info.sender) {
+// guard of admin messages, owner holds every role
+fn assert_role(storage: &dyn Storage, sender: &Addr, role: Role) -> Result<(), ContractError> {
+    if !has_role(storage, &role, sender)? {
         return Err(ContractError::Unauthorized{});
     }
+    Ok(())
+}
 
-    CONFIGS.save(_deps.storage, &Configs{
-        bounty_denom: bounty_denom.clone(),
-        fee,
-        callback_limit_gas,
-        max_callback
+fn assert_not_paused(paused: bool, operation: &str) -> Result<(), ContractError> {
+    if paused {
+        return Err(ContractError::Paused{operation: operation.to_string()});
+    }
+    Ok(())
+}
+
+// treasury does not record denom of its funds, so bounty denom can only change while nothing is held in it
+fn assert_bounty_denom(storage: &dyn Storage, bounty_denom: &str) -> Result<(), ContractError> {
+    if CONFIGS.load(storage)?.bounty_denom != bounty_denom && !TREASURY.may_load(storage)?.unwrap_or_default().is_empty() {
+        return Err(ContractError::TreasuryNotEmpty{});
+    }
+    Ok(())
+}
+
+// default callback gas limit is used for requests that do not set their own, so it must be accepted by max callback gas
+pub fn assert_callback_gas(callback_limit_gas: u64, max_callback_gas: u64) -> Result<(), ContractError> {
+    if callback_limit_gas > max_callback_gas {
+        return Err(ContractError::CallbackGasLimitExceeded{});
+    }
+    Ok(())
+}
+
+fn execute_add_verification_key(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    modulus: HexBinary,
+    exponent: HexBinary,
+    valid_from: Timestamp,
+    valid_until: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    assert_owner(_deps.storage, &_info.sender)?;
+
+    // reject keys that can never verify anything
+    public_key(&modulus, &exponent)?;
+    assert_verification_key_window(valid_from, valid_until)?;
+
+    // key decides which payloads are accepted, so it is timelocked like other configs
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::AddVerificationKey{
+        modulus,
+        exponent,
+        valid_from,
+        valid_until,
     })?;
 
     Ok(Response::new()
-        .add_attribute("action","set_config")
-        .add_attribute("bounty_denom", bounty_denom)
-        .add_attribute("fee", fee)
-        .add_attribute("callback_limit_gas", callback_limit_gas.to_string())
-        .add_attribute("max_callback", max_callback.to_string())
+        .add_attribute("action","add_verification_key")
+        .add_attribute("valid_from", valid_from.seconds().to_string())
+        .add_attribute("valid_until", valid_until.map_or(String::from("none"), |time| time.seconds().to_string()))
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
         .add_attribute("owner",_info.sender))
 }
 
-fn execute_set_time_configs(
+fn execute_set_verification_key_expiry(
     _deps: DepsMut, 
+    _env: Env,
     _info: MessageInfo, 
-    time_expired: u64,
-    time_per_block: u64,
+    id: u64,
+    valid_until: Option<Timestamp>,
 ) -> Result<Response, ContractError> {
-    let owner = OWNER.load(_deps.storage)?;
+    assert_owner(_deps.storage, &_info.sender)?;
 
-    if !owner.eq(&_info.sender) {
-        return Err(ContractError::Unauthorized{});
-    }
+    let key = load_verification_key(_deps.storage, id)?;
+    assert_verification_key_window(key.valid_from, valid_until)?;
 
-    TIME_CONFIGS.save(_deps.storage, &TimeConfigs{
-        time_expired,
-        time_per_block,
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::VerificationKeyExpiry{
+        id,
+        valid_until,
     })?;
 
     Ok(Response::new()
-        .add_attribute("action","set_time_config")
-        .add_attribute("time_expired", time_expired.to_string())
-        .add_attribute("time_per_block", time_per_block.to_string())
+        .add_attribute("action","set_verification_key_expiry")
+        .add_attribute("id", id.to_string())
+        .add_attribute("valid_until", valid_until.map_or(String::from("none"), |time| time.seconds().to_string()))
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
         .add_attribute("owner",_info.sender))
 }
 
-fn execute_set_nois_configs(
+fn execute_revoke_verification_key(
     _deps: DepsMut, 
     _info: MessageInfo, 
-    nois_proxy: Addr, 
-    nois_fee: Uint128,
+    id: u64,
 ) -> Result<Response, ContractError> {
-    let owner = OWNER.load(_deps.storage)?;
+    assert_owner(_deps.storage, &_info.sender)?;
 
-    if !owner.eq(&_info.sender.clone()) {
-        return Err(ContractError::Unauthorized{});
+    let mut key = load_verification_key(_deps.storage, id)?;
+
+    key.revoked = true;
+    VERIFICATION_KEYS.save(_deps.storage, id, &key)?;
+
+    Ok(Response::new()
+        .add_attribute("action","revoke_verification_key")
+        .add_attribute("id", id.to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+fn load_verification_key(storage: &dyn Storage, id: u64) -> Result<VerificationKey, ContractError> {
+    VERIFICATION_KEYS.may_load(storage, id)?.ok_or(ContractError::VerificationKeyNotFound{})
+}
+
+fn assert_verification_key_window(valid_from: Timestamp, valid_until: Option<Timestamp>) -> Result<(), ContractError> {
+    if let Some(valid_until) = valid_until {
+        if valid_until.le(&valid_from) {
+            return Err(ContractError::InvalidVerificationKey{});
+        }
     }
+    Ok(())
+}
 
-    NOIS_CONFIGS.save(_deps.storage, &NoisConfigs{
-        nois_proxy: nois_proxy.clone(),
-        nois_fee,
-    })?;
+fn execute_set_reject_developer_license(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    reject: bool,
+) -> Result<Response, ContractError> {
+    assert_owner(_deps.storage, &_info.sender)?;
+
+    REJECT_DEVELOPER_LICENSE.save(_deps.storage, &reject)?;
 
     Ok(Response::new()
-        .add_attribute("action","set_nois_config")
-        .add_attribute("nois_proxy", nois_proxy.to_string())
-        .add_attribute("nois_fee", nois_fee)
+        .add_attribute("action","set_reject_developer_license")
+        .add_attribute("reject", reject.to_string())
         .add_attribute("owner",_info.sender))
 }
 
-fn execute_register_bot(
+fn execute_set_pause_status(
     _deps: DepsMut, 
-    _env: Env,
     _info: MessageInfo, 
-    hashed_api_key: String, 
-    moniker: String
+    update: PauseStatusUpdate,
 ) -> Result<Response, ContractError> {
-    if BOTS.has(_deps.storage, _info.sender.clone()) {
-        return Err(ContractError::AddressAlreadyRegistered{});
+    assert_role(_deps.storage, &_info.sender, Role::Pauser)?;
+
+    let mut pause_status = PAUSE_STATUS.may_load(_deps.storage)?.unwrap_or_default();
+    pause_status.requests = update.requests.unwrap_or(pause_status.requests);
+    pause_status.random_org = update.random_org.unwrap_or(pause_status.random_org);
+    pause_status.nois = update.nois.unwrap_or(pause_status.nois);
+    pause_status.bot_registration = update.bot_registration.unwrap_or(pause_status.bot_registration);
+    pause_status.expiry_extension = update.expiry_extension.unwrap_or(pause_status.expiry_extension);
+
+    PAUSE_STATUS.save(_deps.storage, &pause_status)?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_pause_status")
+        .add_attribute("requests", pause_status.requests.to_string())
+        .add_attribute("random_org", pause_status.random_org.to_string())
+        .add_attribute("nois", pause_status.nois.to_string())
+        .add_attribute("bot_registration", pause_status.bot_registration.to_string())
+        .add_attribute("expiry_extension", pause_status.expiry_extension.to_string())
+        .add_attribute("pauser",_info.sender))
+}
+
+fn execute_grant_role(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    role: Role,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(_deps.storage, &_info.sender)?;
+
+    ROLES.save(_deps.storage, (role.as_str().to_string(), address.clone()), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action","grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address)
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_revoke_role(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    role: Role,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_owner(_deps.storage, &_info.sender)?;
+
+    let key = (role.as_str().to_string(), address.clone());
+    if !ROLES.has(_deps.storage, key.clone()) {
+        return Err(ContractError::RoleNotGranted{});
     }
 
-    BOTS.save(_deps.storage, _info.sender.clone(), &Bot{
-        address: _info.sender.clone(),
-        hashed_api_key: hashed_api_key.clone(),
-        moniker: moniker.clone(),
-        last_update: _env.block.time,
-    })?;
+    ROLES.remove(_deps.storage, key);
 
-    Ok(Response::new().add_attribute("action","register_bot")
-                    .add_attribute("hashed_api_key", hashed_api_key)
-                    .add_attribute("moniker", moniker)
-                    .add_attribute("bot_address", _info.sender))
+    Ok(Response::new()
+        .add_attribute("action","revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address)
+        .add_attribute("owner",_info.sender))
 }
 
-fn execute_update_bot(
+fn execute_propose_owner(
     _deps: DepsMut, 
     _env: Env,
     _info: MessageInfo, 
-    hashed_api_key: String, 
-    moniker: String
+    new_owner: Addr,
+    expiry: Option<u64>,
 ) -> Result<Response, ContractError> {
-    if !BOTS.has(_deps.storage, _info.sender.clone()) {
-        return Err(ContractError::UnregisteredAddress{});
-    }
+    assert_owner(_deps.storage, &_info.sender)?;
 
-    let time_configs = TIME_CONFIGS.load(_deps.storage)?;
+    let expires_at = expiry.map(|seconds| _env.block.time.plus_seconds(seconds));
 
-    let bot_infor: Bot = BOTS.load(_deps.storage, _info.sender.clone())?;
+    PENDING_OWNER.save(_deps.storage, &PendingOwner{
+        address: new_owner.clone(),
+        expires_at,
+    })?;
 
-    // time conditions for bot update action, delay time between two actions must longer than commitment's expiration time  
-    let bound_time: Timestamp = _env.block.time;
-    let bound_time = bound_time.minus_seconds(time_configs.time_per_block)
-                            .minus_seconds(time_configs.time_expired);
+    let mut res = Response::new()
+        .add_attribute("action","propose_owner")
+        .add_attribute("pending_owner", new_owner)
+        .add_attribute("owner",_info.sender);
 
-    if bound_time.lt(&bot_infor.last_update) {
-        return Err(ContractError::ToManyAction{});
+    if let Some(expires_at) = expires_at {
+        res = res.add_attribute("expires_at", expires_at.seconds().to_string());
     }
 
-    BOTS.save(_deps.storage, _info.sender.clone(), &Bot{
-        address: _info.sender.clone(),
-        hashed_api_key: hashed_api_key.clone(),
-        moniker: moniker.clone(),
-        last_update: _env.block.time,
-    })?;
-    
-    Ok(Response::new().add_attribute("action","update_bot")
-                    .add_attribute("hashed_api_key", hashed_api_key)
-                    .add_attribute("moniker", moniker)
-                    .add_attribute("bot_address", _info.sender))
+    Ok(res)
 }
 
-fn execute_remove_bot(
+fn execute_accept_ownership(
     _deps: DepsMut, 
+    _env: Env,
     _info: MessageInfo, 
-    bot_addr: Addr
 ) -> Result<Response, ContractError> {
-    let owner = OWNER.load(_deps.storage)?;
+    let pending_owner = PENDING_OWNER.may_load(_deps.storage)?
+        .ok_or(ContractError::NoPendingOwner{})?;
+
+    if !pending_owner.address.eq(&_info.sender) {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    if let Some(expires_at) = pending_owner.expires_at {
+        if expires_at.le(&_env.block.time) {
+            return Err(ContractError::OwnershipProposalExpired{});
+        }
+    }
+
+    let previous_owner = OWNER.may_load(_deps.storage)?;
+
+    OWNER.save(_deps.storage, &_info.sender)?;
+    PENDING_OWNER.remove(_deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action","accept_ownership")
+        .add_attribute("previous_owner", previous_owner.map(|owner| owner.to_string()).unwrap_or_default())
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_cancel_ownership_proposal(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+) -> Result<Response, ContractError> {
+    assert_owner(_deps.storage, &_info.sender)?;
+
+    if PENDING_OWNER.may_load(_deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner{});
+    }
+
+    PENDING_OWNER.remove(_deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action","cancel_ownership_proposal")
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_renounce_ownership(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+) -> Result<Response, ContractError> {
+    assert_owner(_deps.storage, &_info.sender)?;
+
+    // nobody can manage contract configs after this
+    OWNER.remove(_deps.storage);
+    PENDING_OWNER.remove(_deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action","renounce_ownership")
+        .add_attribute("previous_owner",_info.sender))
+}
+
+fn execute_set_configs(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    configs: Configs,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::ConfigAdmin)?;
+
+    if configs.protocol_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFeeSplit{});
+    }
+
+    assert_callback_gas(configs.callback_limit_gas, GAS_CONFIGS.load(_deps.storage)?.max_callback_gas)?;
+    assert_bounty_denom(_deps.storage, &configs.bounty_denom)?;
+
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::Configs(configs.clone()))?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_config")
+        .add_attribute("bounty_denom", configs.bounty_denom)
+        .add_attribute("fee", configs.fee)
+        .add_attribute("callback_limit_gas", configs.callback_limit_gas.to_string())
+        .add_attribute("max_callback", configs.max_callback.to_string())
+        .add_attribute("keeper_reward", configs.keeper_reward)
+        .add_attribute("protocol_fee_bps", configs.protocol_fee_bps.to_string())
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_set_time_configs(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    time_expired: u64,
+    time_per_block: u64,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::ConfigAdmin)?;
+
+    TIME_CONFIGS.save(_deps.storage, &TimeConfigs{
+        time_expired,
+        time_per_block,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_time_config")
+        .add_attribute("time_expired", time_expired.to_string())
+        .add_attribute("time_per_block", time_per_block.to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_set_gas_configs(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    max_callback_gas: u64,
+    callback_gas_price: Decimal,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::ConfigAdmin)?;
+
+    assert_callback_gas(CONFIGS.load(_deps.storage)?.callback_limit_gas, max_callback_gas)?;
+
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::GasConfigs(GasConfigs{
+        max_callback_gas,
+        callback_gas_price,
+    }))?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_gas_config")
+        .add_attribute("max_callback_gas", max_callback_gas.to_string())
+        .add_attribute("callback_gas_price", callback_gas_price.to_string())
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_set_staking_configs(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    staking_configs: StakingConfigs,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::ConfigAdmin)?;
+
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::StakingConfigs(staking_configs.clone()))?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_staking_config")
+        .add_attribute("min_stake", staking_configs.min_stake)
+        .add_attribute("unbonding_period", staking_configs.unbonding_period.to_string())
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_set_api_key_policy(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    api_key_policy: ApiKeyPolicy,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::ConfigAdmin)?;
+
+    if api_key_policy.max_api_keys == 0 {
+        return Err(ContractError::InvalidApiKeyPolicy{});
+    }
+
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::ApiKeyPolicy(api_key_policy.clone()))?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_api_key_policy")
+        .add_attribute("update_cooldown", api_key_policy.update_cooldown.to_string())
+        .add_attribute("grace_period", api_key_policy.grace_period.to_string())
+        .add_attribute("max_api_keys", api_key_policy.max_api_keys.to_string())
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_set_nois_configs(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    nois_proxy: Addr, 
+    nois_fee: Uint128,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::NoisAdmin)?;
+
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::NoisConfigs(NoisConfigs{
+        nois_proxy: nois_proxy.clone(),
+        nois_fee,
+    }))?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_nois_config")
+        .add_attribute("nois_proxy", nois_proxy.to_string())
+        .add_attribute("nois_fee", nois_fee)
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+fn execute_set_config_change_delay(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    delay: u64,
+) -> Result<Response, ContractError> {
+    assert_owner(_deps.storage, &_info.sender)?;
+
+    // delay itself is timelocked, so it can not be shortened to skip the notice period
+    let scheduled = schedule_config_change(_deps.storage, &_env, &_info.sender, ConfigChange::Delay(delay))?;
+
+    Ok(Response::new()
+        .add_attribute("action","set_config_change_delay")
+        .add_attribute("delay", delay.to_string())
+        .add_attribute("change_id", scheduled.id.to_string())
+        .add_attribute("effective_time", scheduled.effective_time.seconds().to_string())
+        .add_attribute("owner",_info.sender))
+}
+
+// queue config change, it can be executed after config change delay and before its execution window closes
+// change is applied right away if there is no delay
+fn schedule_config_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposer: &Addr,
+    change: ConfigChange,
+) -> Result<ScheduledConfigChange, ContractError> {
+    let delay = CONFIG_CHANGE_DELAY.may_load(storage)?.unwrap_or_default();
+    let id = CONFIG_CHANGE_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+
+    // a newer change replaces queued changes of the same target, so an older one can not revert it later
+    let replaced_ids: Vec<u64> = SCHEDULED_CONFIG_CHANGES
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|(_, scheduled)| scheduled.change.target() == change.target())
+        .map(|(id, _)| id)
+        .collect();
+    for replaced_id in replaced_ids {
+        SCHEDULED_CONFIG_CHANGES.remove(storage, replaced_id);
+    }
+
+    let effective_time = env.block.time.plus_seconds(delay);
+    let scheduled = ScheduledConfigChange {
+        id,
+        change,
+        proposer: proposer.clone(),
+        scheduled_time: env.block.time,
+        effective_time,
+        expired_time: effective_time.plus_seconds(CONFIG_CHANGE_EXECUTION_WINDOW),
+    };
+
+    CONFIG_CHANGE_COUNT.save(storage, &id)?;
+    if delay == 0 {
+        apply_config_change(storage, &scheduled.change)?;
+    } else {
+        SCHEDULED_CONFIG_CHANGES.save(storage, id, &scheduled)?;
+    }
+
+    Ok(scheduled)
+}
+
+// save new value of a config change
+// state may have changed since scheduling, so callback gas and bounty denom are checked again
+fn apply_config_change(storage: &mut dyn Storage, change: &ConfigChange) -> Result<(), ContractError> {
+    match change {
+        ConfigChange::Configs(configs) => {
+            assert_callback_gas(configs.callback_limit_gas, GAS_CONFIGS.load(storage)?.max_callback_gas)?;
+            assert_bounty_denom(storage, &configs.bounty_denom)?;
+            CONFIGS.save(storage, configs)?
+        },
+        ConfigChange::NoisConfigs(nois_configs) => NOIS_CONFIGS.save(storage, nois_configs)?,
+        ConfigChange::GasConfigs(gas_configs) => {
+            assert_callback_gas(CONFIGS.load(storage)?.callback_limit_gas, gas_configs.max_callback_gas)?;
+            GAS_CONFIGS.save(storage, gas_configs)?
+        },
+        ConfigChange::StakingConfigs(staking_configs) => STAKING_CONFIGS.save(storage, staking_configs)?,
+        ConfigChange::ApiKeyPolicy(api_key_policy) => API_KEY_POLICY.save(storage, api_key_policy)?,
+        ConfigChange::Delay(delay) => CONFIG_CHANGE_DELAY.save(storage, delay)?,
+        ConfigChange::AddVerificationKey { modulus, exponent, valid_from, valid_until } => {
+            add_verification_key(storage, modulus.clone(), exponent.clone(), *valid_from, *valid_until)?;
+        },
+        ConfigChange::VerificationKeyExpiry { id, valid_until } => {
+            let mut key = load_verification_key(storage, *id)?;
+            key.valid_until = *valid_until;
+            VERIFICATION_KEYS.save(storage, *id, &key)?
+        },
+    }
+    Ok(())
+}
+
+fn execute_execute_config_change(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    change_id: u64,
+) -> Result<Response, ContractError> {
+    let scheduled = SCHEDULED_CONFIG_CHANGES.may_load(_deps.storage, change_id)?
+        .ok_or(ContractError::ConfigChangeNotFound{})?;
+
+    if scheduled.effective_time.gt(&_env.block.time) {
+        return Err(ContractError::ConfigChangeNotDue{});
+    }
+
+    // stale change must be scheduled again
+    if scheduled.expired_time.lt(&_env.block.time) {
+        return Err(ContractError::ConfigChangeExpired{});
+    }
+
+    // anyone can apply a change once its notice period is over
+    apply_config_change(_deps.storage, &scheduled.change)?;
+    SCHEDULED_CONFIG_CHANGES.remove(_deps.storage, change_id);
+
+    Ok(Response::new()
+        .add_attribute("action","execute_config_change")
+        .add_attribute("change_id", change_id.to_string())
+        .add_attribute("kind", scheduled.change.kind())
+        .add_attribute("executor",_info.sender))
+}
+
+fn execute_cancel_config_change(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    change_id: u64,
+) -> Result<Response, ContractError> {
+    let scheduled = SCHEDULED_CONFIG_CHANGES.may_load(_deps.storage, change_id)?
+        .ok_or(ContractError::ConfigChangeNotFound{})?;
+
+    // change can be cancelled by anyone who can schedule it
+    match scheduled.change.role() {
+        Some(role) => assert_role(_deps.storage, &_info.sender, role)?,
+        None => assert_owner(_deps.storage, &_info.sender)?,
+    }
+
+    SCHEDULED_CONFIG_CHANGES.remove(_deps.storage, change_id);
+
+    Ok(Response::new()
+        .add_attribute("action","cancel_config_change")
+        .add_attribute("change_id", change_id.to_string())
+        .add_attribute("kind", scheduled.change.kind())
+        .add_attribute("sender",_info.sender))
+}
+
+fn execute_register_bot(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    hashed_api_key: String, 
+    moniker: String
+) -> Result<Response, ContractError> {
+    let pause_status = PAUSE_STATUS.may_load(_deps.storage)?.unwrap_or_default();
+    assert_not_paused(pause_status.bot_registration, "bot_registration")?;
+
+    if BOTS.has(_deps.storage, _info.sender.clone()) {
+        return Err(ContractError::AddressAlreadyRegistered{});
+    }
+
+    // removed bot has to wait until its tombstone expires
+    if let Some(tombstone) = BOT_TOMBSTONES.may_load(_deps.storage, _info.sender.clone())? {
+        if _env.block.time.lt(&tombstone.reregister_time) {
+            return Err(ContractError::BotDeregistered{});
+        }
+    }
+    assert_api_key_available(_deps.storage, &_env, &_info.sender, &hashed_api_key)?;
+
+    // bot must back its work with stake
+    let bonded = bond_stake(_deps.storage, &_info)?;
+    let staking_configs = STAKING_CONFIGS.load(_deps.storage)?;
+    if bonded < staking_configs.min_stake {
+        return Err(ContractError::InsufficientStake{});
+    }
+
+    // in allowlist mode bot can not add randomness until bot manager approves it
+    let status = match REGISTRATION_MODE.may_load(_deps.storage)?.unwrap_or_default() {
+        RegistrationMode::Open => BotStatus::Active,
+        RegistrationMode::Allowlist => BotStatus::PendingApproval,
+    };
+
+    BOTS.save(_deps.storage, _info.sender.clone(), &Bot{
+        address: _info.sender.clone(),
+        api_keys: vec![ApiKey{
+            hashed_api_key: hashed_api_key.clone(),
+            added_time: _env.block.time,
+            valid_until: None,
+        }],
+        moniker: moniker.clone(),
+        last_update: _env.block.time,
+        status: status.clone(),
+    })?;
+    BOTS_BY_STATUS.save(_deps.storage, (status.as_str().to_string(), _info.sender.clone()), &true)?;
+    API_KEY_OWNERS.save(_deps.storage, hashed_api_key.clone(), &_info.sender)?;
+
+    Ok(Response::new().add_attribute("action","register_bot")
+                    .add_attribute("hashed_api_key", hashed_api_key)
+                    .add_attribute("moniker", moniker)
+                    .add_attribute("bonded", bonded)
+                    .add_attribute("status", status.as_str())
+                    .add_attribute("bot_address", _info.sender))
+}
+
+fn execute_update_bot(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    hashed_api_key: String, 
+    moniker: String
+) -> Result<Response, ContractError> {
+    let mut bot = BOTS.may_load(_deps.storage, _info.sender.clone())?
+        .ok_or(ContractError::UnregisteredAddress{})?;
+
+    let api_key_policy = prepare_api_key_change(_deps.storage, &_env, &mut bot)?;
+    assert_api_key_available(_deps.storage, &_env, &_info.sender, &hashed_api_key)?;
+
+    // keys in use are retired, payloads of in-flight commitments signed under them are still accepted during grace period
+    let valid_until = _env.block.time.plus_seconds(api_key_policy.grace_period);
+    for api_key in bot.api_keys.iter_mut() {
+        if api_key.hashed_api_key != hashed_api_key && api_key.valid_until.is_none() {
+            api_key.valid_until = Some(valid_until);
+        }
+    }
+    activate_api_key(&_env, &mut bot, &hashed_api_key, &api_key_policy)?;
+
+    bot.moniker = moniker.clone();
+    bot.last_update = _env.block.time;
+    BOTS.save(_deps.storage, _info.sender.clone(), &bot)?;
+    API_KEY_OWNERS.save(_deps.storage, hashed_api_key.clone(), &_info.sender)?;
+    
+    Ok(Response::new().add_attribute("action","update_bot")
+                    .add_attribute("hashed_api_key", hashed_api_key)
+                    .add_attribute("moniker", moniker)
+                    .add_attribute("bot_address", _info.sender))
+}
+
+fn execute_add_api_key(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    hashed_api_key: String, 
+) -> Result<Response, ContractError> {
+    let mut bot = BOTS.may_load(_deps.storage, _info.sender.clone())?
+        .ok_or(ContractError::UnregisteredAddress{})?;
+
+    let api_key_policy = prepare_api_key_change(_deps.storage, &_env, &mut bot)?;
+
+    if bot.api_keys.iter().any(|api_key| api_key.hashed_api_key == hashed_api_key && api_key.valid_until.is_none()) {
+        return Err(ContractError::ApiKeyAlreadyAdded{});
+    }
+    assert_api_key_available(_deps.storage, &_env, &_info.sender, &hashed_api_key)?;
+    activate_api_key(&_env, &mut bot, &hashed_api_key, &api_key_policy)?;
+
+    bot.last_update = _env.block.time;
+    BOTS.save(_deps.storage, _info.sender.clone(), &bot)?;
+    API_KEY_OWNERS.save(_deps.storage, hashed_api_key.clone(), &_info.sender)?;
+
+    Ok(Response::new().add_attribute("action","add_api_key")
+                    .add_attribute("hashed_api_key", hashed_api_key)
+                    .add_attribute("bot_address", _info.sender))
+}
+
+fn execute_retire_api_key(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    hashed_api_key: String, 
+) -> Result<Response, ContractError> {
+    let mut bot = BOTS.may_load(_deps.storage, _info.sender.clone())?
+        .ok_or(ContractError::UnregisteredAddress{})?;
+
+    let api_key_policy = prepare_api_key_change(_deps.storage, &_env, &mut bot)?;
+
+    // bot must keep a key in use to sign new payloads
+    if bot.api_keys.iter().filter(|api_key| api_key.valid_until.is_none()).count() <= 1 {
+        return Err(ContractError::LastApiKey{});
+    }
+
+    let valid_until = _env.block.time.plus_seconds(api_key_policy.grace_period);
+    let api_key = bot.api_keys.iter_mut()
+        .find(|api_key| api_key.hashed_api_key == hashed_api_key && api_key.valid_until.is_none())
+        .ok_or(ContractError::ApiKeyNotFound{})?;
+    api_key.valid_until = Some(valid_until);
+
+    bot.last_update = _env.block.time;
+    BOTS.save(_deps.storage, _info.sender.clone(), &bot)?;
+
+    Ok(Response::new().add_attribute("action","retire_api_key")
+                    .add_attribute("hashed_api_key", hashed_api_key)
+                    .add_attribute("valid_until", valid_until.seconds().to_string())
+                    .add_attribute("bot_address", _info.sender))
+}
+
+// check api key change cooldown of bot and drop retired keys whose grace period is over
+fn prepare_api_key_change(storage: &dyn Storage, env: &Env, bot: &mut Bot) -> Result<ApiKeyPolicy, ContractError> {
+    let api_key_policy = API_KEY_POLICY.load(storage)?;
+
+    if env.block.time.lt(&bot.last_update.plus_seconds(api_key_policy.update_cooldown)) {
+        return Err(ContractError::ToManyAction{});
+    }
+
+    bot.api_keys.retain(|api_key| api_key.is_valid(env.block.time));
+
+    Ok(api_key_policy)
+}
+
+// api key can not be shared with another registered bot, and keys of removed bot can not be used again until its tombstone expires
+fn assert_api_key_available(storage: &dyn Storage, env: &Env, bot_addr: &Addr, hashed_api_key: &str) -> Result<(), ContractError> {
+    if let Some(owner) = API_KEY_OWNERS.may_load(storage, hashed_api_key.to_string())? {
+        if !owner.eq(bot_addr) {
+            let holds_key = BOTS.may_load(storage, owner)?
+                .is_some_and(|bot| bot.api_keys.iter().any(|api_key| api_key.hashed_api_key == hashed_api_key && api_key.is_valid(env.block.time)));
+            if holds_key {
+                return Err(ContractError::ApiKeyInUse{});
+            }
+        }
+    }
+
+    if let Some(reregister_time) = API_KEY_TOMBSTONES.may_load(storage, hashed_api_key.to_string())? {
+        if env.block.time.lt(&reregister_time) {
+            return Err(ContractError::ApiKeyDeregistered{});
+        }
+    }
+    Ok(())
+}
+
+// put key in use, a retired key is brought back without waiting for its grace period to end
+fn activate_api_key(env: &Env, bot: &mut Bot, hashed_api_key: &str, api_key_policy: &ApiKeyPolicy) -> Result<(), ContractError> {
+    match bot.api_keys.iter_mut().find(|api_key| api_key.hashed_api_key == hashed_api_key) {
+        Some(api_key) => api_key.valid_until = None,
+        None => bot.api_keys.push(ApiKey{
+            hashed_api_key: hashed_api_key.to_string(),
+            added_time: env.block.time,
+            valid_until: None,
+        }),
+    }
+
+    if bot.api_keys.len() > api_key_policy.max_api_keys as usize {
+        return Err(ContractError::TooManyApiKeys{});
+    }
+
+    Ok(())
+}
+
+fn execute_remove_bot(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    bot_addr: Addr
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::BotManager)?;
+
+    // removed bot can take its stake back after unbonding period, it is still slashable until then
+    let staking_configs = STAKING_CONFIGS.load(_deps.storage)?;
+    let release_time = _env.block.time.plus_seconds(staking_configs.unbonding_period);
+
+    if let Some(bot) = BOTS.may_load(_deps.storage, bot_addr.clone())? {
+        tombstone_bot(_deps.storage, &bot, _env.block.time, release_time)?;
+        BOTS.remove(_deps.storage, bot_addr.clone());
+        BOTS_BY_STATUS.remove(_deps.storage, (bot.status.as_str().to_string(), bot_addr.clone()));
+    }
+
+    let unbonded = unbond_all(_deps.storage, &bot_addr, release_time)?;
+
+    Ok(Response::new().add_attribute("action","remove_bot")
+                    .add_attribute("bot_addr", bot_addr)
+                    .add_attribute("unbonding", unbonded)
+                    .add_attribute("owner",_info.sender))
+}
+
+fn execute_deregister_bot(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let bot = BOTS.may_load(_deps.storage, _info.sender.clone())?
+        .ok_or(ContractError::UnregisteredAddress{})?;
+
+    // bot that has never been approved has not worked, so its stake is released without unbonding period
+    let release_time = match bot.status {
+        BotStatus::Active => _env.block.time.plus_seconds(STAKING_CONFIGS.load(_deps.storage)?.unbonding_period),
+        BotStatus::PendingApproval => _env.block.time,
+    };
+
+    // address and api keys can not register again while stake is still slashable
+    tombstone_bot(_deps.storage, &bot, _env.block.time, release_time)?;
+    BOTS.remove(_deps.storage, _info.sender.clone());
+    BOTS_BY_STATUS.remove(_deps.storage, (bot.status.as_str().to_string(), _info.sender.clone()));
+
+    let unbonded = unbond_all(_deps.storage, &_info.sender, release_time)?;
+
+    let rewards = take_bot_rewards(_deps.storage, &_info.sender)?;
+    let recipient = recipient.unwrap_or_else(|| _info.sender.clone());
+
+    let mut res = Response::new();
+    if !rewards.is_zero() {
+        let configs = CONFIGS.load(_deps.storage)?;
+        res = res.add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(rewards.into(), configs.bounty_denom),
+        });
+    }
+
+    Ok(res.add_attribute("action","deregister_bot")
+        .add_attribute("rewards", rewards)
+        .add_attribute("recipient", recipient)
+        .add_attribute("unbonding", unbonded)
+        .add_attribute("reregister_time", release_time.seconds().to_string())
+        .add_attribute("bot_address", _info.sender))
+}
+
+// move whole bonded stake of bot to unbonding
+fn unbond_all(storage: &mut dyn Storage, bot_addr: &Addr, release_time: Timestamp) -> StdResult<Uint128> {
+    let mut stake = BOT_STAKES.may_load(storage, bot_addr.clone())?.unwrap_or_default();
+    let unbonded = stake.bonded;
+    if !unbonded.is_zero() {
+        stake.unbonding.push(Unbonding {
+            amount: unbonded,
+            release_time,
+        });
+        stake.bonded = Uint128::zero();
+        BOT_STAKES.save(storage, bot_addr.clone(), &stake)?;
+    }
+    Ok(unbonded)
+}
+
+fn registration_mode_str(mode: &RegistrationMode) -> &'static str {
+    match mode {
+        RegistrationMode::Open => "open",
+        RegistrationMode::Allowlist => "allowlist",
+    }
+}
+
+fn execute_set_registration_mode(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    mode: RegistrationMode,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::BotManager)?;
+
+    // bots registered before are not affected
+    REGISTRATION_MODE.save(_deps.storage, &mode)?;
+
+    Ok(Response::new().add_attribute("action","set_registration_mode")
+                    .add_attribute("mode", registration_mode_str(&mode))
+                    .add_attribute("owner",_info.sender))
+}
+
+fn execute_approve_bot(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    bot_addr: Addr
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::BotManager)?;
+
+    let mut bot = BOTS.may_load(_deps.storage, bot_addr.clone())?
+        .ok_or(ContractError::UnregisteredAddress{})?;
+    if bot.status != BotStatus::PendingApproval {
+        return Err(ContractError::BotNotPending{});
+    }
+
+    bot.status = BotStatus::Active;
+    BOTS.save(_deps.storage, bot_addr.clone(), &bot)?;
+    BOTS_BY_STATUS.remove(_deps.storage, (BotStatus::PendingApproval.as_str().to_string(), bot_addr.clone()));
+    BOTS_BY_STATUS.save(_deps.storage, (BotStatus::Active.as_str().to_string(), bot_addr.clone()), &true)?;
+
+    Ok(Response::new().add_attribute("action","approve_bot")
+                    .add_attribute("bot_addr", bot_addr)
+                    .add_attribute("owner",_info.sender))
+}
+
+fn execute_reject_bot(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    bot_addr: Addr
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::BotManager)?;
+
+    let bot = BOTS.may_load(_deps.storage, bot_addr.clone())?
+        .ok_or(ContractError::UnregisteredAddress{})?;
+    if bot.status != BotStatus::PendingApproval {
+        return Err(ContractError::BotNotPending{});
+    }
+
+    BOTS.remove(_deps.storage, bot_addr.clone());
+    BOTS_BY_STATUS.remove(_deps.storage, (bot.status.as_str().to_string(), bot_addr.clone()));
+
+    // rejected bot has never worked, so its stake is released without unbonding period
+    let unbonded = unbond_all(_deps.storage, &bot_addr, _env.block.time)?;
+
+    Ok(Response::new().add_attribute("action","reject_bot")
+                    .add_attribute("bot_addr", bot_addr)
+                    .add_attribute("unbonding", unbonded)
+                    .add_attribute("owner",_info.sender))
+}
+
+// add funds of message to bonded stake of sender, only bounty denom is accepted
+fn bond_stake(storage: &mut dyn Storage, _info: &MessageInfo) -> Result<Uint128, ContractError> {
+    let configs = CONFIGS.load(storage)?;
+
+    let mut amount = Uint128::zero();
+    for coin in _info.funds.iter() {
+        if !coin.denom.eq(&configs.bounty_denom) {
+            return Err(ContractError::UnexpectedDenom{denom: coin.denom.clone()});
+        }
+        amount = amount.checked_add(coin.amount)
+            .map_err(|_| ContractError::Uint128Overflow{})?;
+    }
+
+    let mut stake = BOT_STAKES.may_load(storage, _info.sender.clone())?.unwrap_or_default();
+    stake.bonded = stake.bonded.checked_add(amount)
+        .map_err(|_| ContractError::Uint128Overflow{})?;
+    BOT_STAKES.save(storage, _info.sender.clone(), &stake)?;
+
+    let mut treasury = TREASURY.may_load(storage)?.unwrap_or_default();
+    treasury.staked = treasury.staked.checked_add(amount)
+        .map_err(|_| ContractError::Uint128Overflow{})?;
+    TREASURY.save(storage, &treasury)?;
+
+    Ok(stake.bonded)
+}
+
+fn execute_bond_stake(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+) -> Result<Response, ContractError> {
+    if _info.funds.is_empty() {
+        return Err(ContractError::InsufficientStake{});
+    }
+
+    let bonded = bond_stake(_deps.storage, &_info)?;
+
+    Ok(Response::new().add_attribute("action","bond_stake")
+                    .add_attribute("bonded", bonded)
+                    .add_attribute("bot", _info.sender))
+}
+
+fn execute_unbond_stake(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut stake = BOT_STAKES.may_load(_deps.storage, _info.sender.clone())?.unwrap_or_default();
+    if amount.is_zero() || amount > stake.bonded {
+        return Err(ContractError::InsufficientStake{});
+    }
+
+    let staking_configs = STAKING_CONFIGS.load(_deps.storage)?;
+    let release_time = _env.block.time.plus_seconds(staking_configs.unbonding_period);
+
+    stake.bonded -= amount;
+    stake.unbonding.push(Unbonding {
+        amount,
+        release_time,
+    });
+    BOT_STAKES.save(_deps.storage, _info.sender.clone(), &stake)?;
+
+    Ok(Response::new().add_attribute("action","unbond_stake")
+                    .add_attribute("amount", amount)
+                    .add_attribute("release_time", release_time.seconds().to_string())
+                    .add_attribute("bonded", stake.bonded)
+                    .add_attribute("bot", _info.sender))
+}
+
+fn execute_withdraw_stake(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+) -> Result<Response, ContractError> {
+    let mut stake = BOT_STAKES.may_load(_deps.storage, _info.sender.clone())?.unwrap_or_default();
+
+    // only stake whose unbonding period is over can be withdrawn
+    let mut amount = Uint128::zero();
+    for unbonding in stake.unbonding.iter().filter(|unbonding| unbonding.release_time.le(&_env.block.time)) {
+        amount += unbonding.amount;
+    }
+    if amount.is_zero() {
+        return Err(ContractError::NoUnbondedStake{});
+    }
+
+    stake.unbonding.retain(|unbonding| unbonding.release_time.gt(&_env.block.time));
+    BOT_STAKES.save(_deps.storage, _info.sender.clone(), &stake)?;
+
+    let mut treasury = TREASURY.may_load(_deps.storage)?.unwrap_or_default();
+    treasury.staked = treasury.staked.checked_sub(amount)
+        .map_err(|_| ContractError::Uint128Overflow{})?;
+    TREASURY.save(_deps.storage, &treasury)?;
+
+    let configs = CONFIGS.load(_deps.storage)?;
+
+    Ok(Response::new().add_message(BankMsg::Send {
+                    to_address: _info.sender.to_string(),
+                    amount: coins(amount.into(), configs.bounty_denom),
+                })
+                .add_attribute("action","withdraw_stake")
+                .add_attribute("amount", amount)
+                .add_attribute("bot", _info.sender))
+}
+
+fn execute_slash_bot(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    bot_addr: Addr,
+    amount: Uint128,
+    reason: String,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::BotManager)?;
+
+    let slashed = slash_stake(_deps.storage, &bot_addr, amount, reason.clone(), _env.block.time)?;
+    if slashed.is_zero() {
+        return Err(ContractError::InsufficientStake{});
+    }
+
+    Ok(Response::new().add_attribute("action","slash_bot")
+                    .add_attribute("bot_addr", bot_addr)
+                    .add_attribute("slashed", slashed)
+                    .add_attribute("reason", reason)
+                    .add_attribute("owner", _info.sender))
+}
+
+// delivery settings chosen by user for a randomness request
+struct RequestOptions {
+    delivery_mode: DeliveryMode,
+    callback_address: Option<Addr>, // receiver of callback, default is sender of request
+    callback_payload: Option<Binary>, // opaque data echoed back in callback
+    callback_gas_limit: Option<u64>, // gas limit of callback, default is callback_limit_gas
+}
+
+// price of a randomness request, shared by request handler and price query
+struct RequestPrice {
+    fee: Uint128, // aurand fee of commitment, including callback gas
+    nois_fee: Uint128,
+    total_fee: Uint128, // amount that user must send
+    denom: String,
+    callback_gas_limit: u64,
+}
+
+fn calculate_request_price(
+    storage: &dyn Storage,
+    data_request: &DataRequest,
+    delivery_mode: &DeliveryMode,
+    callback_gas_limit: Option<u64>,
+) -> Result<RequestPrice, ContractError> {
+    // number of user required randomness must in range(MIN_NUM, MAX_NUM) 
+    if data_request.num < MIN_NUM || data_request.num > MAX_NUM {
+        return Err(ContractError::CustomError{val:String::from("number of randomness must be in range ")
+                                                + &MIN_NUM.to_string() 
+                                                + ".." 
+                                                + &MAX_NUM.to_string()});
+    }
+
+    if data_request.data_type != HEX_DATA_TYPE && data_request.data_type != INT_DATA_TYPE {
+        return Err(ContractError::CustomError{val: String::from("Invalid data type!")});
+    }
+
+    let configs = CONFIGS.load(storage)?;
+    let nois_configs = NOIS_CONFIGS.load(storage)?;
+    let gas_configs = GAS_CONFIGS.load(storage)?;
+
+    // pull mode commitment has no callback, so no callback gas is reserved for it
+    let callback_gas_limit = match delivery_mode {
+        DeliveryMode::Callback => callback_gas_limit.unwrap_or(configs.callback_limit_gas),
+        DeliveryMode::Pull => 0,
+    };
 
-    if !owner.eq(&_info.sender) {
-        return Err(ContractError::Unauthorized{});
+    if callback_gas_limit > gas_configs.max_callback_gas {
+        return Err(ContractError::CallbackGasLimitExceeded{});
     }
 
+    // aurand fee of commitment is base fee plus price of callback gas, rounded up
+    let gas_fee = Decimal::from_ratio(callback_gas_limit, 1u64)
+        .checked_mul(gas_configs.callback_gas_price)
+        .map_err(|_| ContractError::Uint128Overflow{})?
+        .to_uint_ceil();
+    let fee = configs.fee.checked_add(gas_fee)
+        .map_err(|_| ContractError::Uint128Overflow{})?;
 
-    BOTS.remove(_deps.storage, bot_addr.clone());
+    // total_fee is calculated by combining nois proxy contract fee and aurand contract fee for each request randomness
+    let total_fee = fee.checked_add(nois_configs.nois_fee)
+        .map_err(|_| ContractError::Uint128Overflow{})?; 
 
-    Ok(Response::new().add_attribute("action","remove_bot")
-                    .add_attribute("bot_addr", bot_addr)
-                    .add_attribute("owner",_info.sender))
+    Ok(RequestPrice {
+        fee,
+        nois_fee: nois_configs.nois_fee,
+        total_fee,
+        denom: configs.bounty_denom,
+        callback_gas_limit,
+    })
 }
 
 fn execute_request_randomness(
@@ -334,22 +1696,22 @@ fn execute_request_randomness(
     _info: MessageInfo,
     request_id: String,
     data_request: DataRequest,
+    options: RequestOptions,
 ) -> Result<Response, ContractError> {
-
-    // number of user required randomness must in range(MIN_NUM, MAX_NUM) 
-    if data_request.num < MIN_NUM || data_request.num > MAX_NUM {
-        return Err(ContractError::CustomError{val:String::from("number of randomness must be in range ")
-                                                + &MIN_NUM.to_string() 
-                                                + &"..".to_string() 
-                                                + &MAX_NUM.to_string()});
-    }
-
-    let configs = CONFIGS.load(_deps.storage)?;
+    let pause_status = PAUSE_STATUS.may_load(_deps.storage)?.unwrap_or_default();
+    assert_not_paused(pause_status.requests, "requests")?;
+
+    let price = calculate_request_price(
+        _deps.storage,
+        &data_request,
+        &options.delivery_mode,
+        options.callback_gas_limit,
+    )?;
     let time_configs = TIME_CONFIGS.load(_deps.storage)?;
     let nois_configs = NOIS_CONFIGS.load(_deps.storage)?;
 
     // check denom and get amount
-    let denom = configs.bounty_denom;
+    let denom = price.denom;
     let matching_coin = _info.funds.iter().find(|fund| fund.denom.eq(&denom));
     let sent_amount: Uint128 = match matching_coin {
         Some(coin) => coin.amount,
@@ -360,14 +1722,16 @@ fn execute_request_randomness(
         }
     };
 
-    // total_fee is calculated by combining nois proxy contract fee and aurand contract fee for each request randomness
-    let total_fee = configs.fee.checked_add(nois_configs.nois_fee)
-        .map_err(|_| ContractError::Uint128Overflow{})?; 
+    // other denoms would be locked in contract forever
+    if let Some(coin) = _info.funds.iter().find(|fund| !fund.denom.eq(&denom)) {
+        return Err(ContractError::UnexpectedDenom{denom: coin.denom.clone()});
+    }
 
-    if sent_amount < total_fee {
+    if sent_amount < price.total_fee {
         return Err(ContractError::CustomError{val: String::from("Insufficient fee! required ") 
-                                                + &total_fee.to_string() + &denom});
+                                                + &price.total_fee.to_string() + &denom});
     }
+    let surplus = sent_amount - price.total_fee;
 
     let mut nonce: u64 = 0;
 
@@ -384,20 +1748,29 @@ fn execute_request_randomness(
     let block_time = _env.block.time;
     let commit_time =  Timestamp::from_seconds(block_time.seconds())
                             .plus_seconds(time_configs.time_per_block);
-    let expired_time = commit_time.clone()
+    let mut expired_time = commit_time.clone()
                     .plus_seconds(time_configs.time_expired);
 
+    // commitment may not be fulfilled while a randomness source is paused, give it more time before it can be reclaimed
+    if pause_status.random_org || pause_status.nois {
+        expired_time = expired_time.plus_seconds(pause_status.expiry_extension);
+    }
+
     let commitment: Commitment = Commitment {
         id: commit_id.clone(),
         request_id: request_id.clone(),
         owner: _info.sender.clone(),
         commit_time,
         expired_time,
-        data_request
+        data_request,
+        fee: price.fee,
+        delivery_mode: options.delivery_mode,
+        callback_address: options.callback_address.unwrap_or_else(|| _info.sender.clone()),
+        callback_payload: options.callback_payload,
+        callback_gas_limit: price.callback_gas_limit,
     };
 
-    COMMITMENTS.push_front(_deps.storage, &(commitment.clone()))?;
-    PENDING_COMMITMENTS.save(_deps.storage, commit_id.clone(), &commitment)?;
+    add_commitment(_deps.storage, &commitment)?;
 
     // nonces[address] which was incremented by the above
     // successful RequestRandomnesss.
@@ -414,7 +1787,7 @@ fn execute_request_randomness(
             contract_addr: nois_configs.nois_proxy.into(),
             msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
                             job_id: commit_id.clone() })?,
-            funds: coins(nois_configs.nois_fee.into(), denom),
+            funds: coins(price.nois_fee.into(), denom.clone()),
         }
         .into(),
         id: NOIS_CALLBACK_REPLY_ID,
@@ -422,185 +1795,271 @@ fn execute_request_randomness(
         reply_on: ReplyOn::Always,
     };
 
-
-    Ok(Response::new().add_submessage(sub_msg)
+    let mut res = Response::new().add_submessage(sub_msg)
             .add_attribute("action", "request_randomness")
             .add_attribute("commitment_id",commit_id)
             .add_attribute("request_id",request_id)
-            .add_attribute("user", _info.sender))
-}
+            .add_attribute("fee", price.fee)
+            .add_attribute("callback_gas_limit", price.callback_gas_limit.to_string())
+            .add_attribute("user", _info.sender.clone());
 
+    // give back overpaid amount to sender
+    if !surplus.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: _info.sender.to_string(),
+            amount: coins(surplus.into(), denom),
+        })
+        .add_attribute("refund", surplus);
+    }
 
-fn execute_request_hex_randomness(
-    _deps: DepsMut, 
-    _env: Env, 
-    _info: MessageInfo, 
-    request_id: String,
-    num: u32
-) -> Result<Response, ContractError> {
-    execute_request_randomness(_deps, _env, _info, request_id, 
-        DataRequest {data_type: HEX_DATA_TYPE.to_string(), min: 0, max: 0, num})
-}
-
-fn execute_request_int_randomness(
-    _deps: DepsMut, 
-    _env: Env, 
-    _info: MessageInfo,
-    request_id: String, 
-    min: i32, 
-    max: i32, 
-    num: u32
-) -> Result<Response, ContractError> {
-    execute_request_randomness(_deps, _env, _info, request_id,
-        DataRequest {data_type: INT_DATA_TYPE.to_string(), min, max, num})
+    Ok(res)
 }
 
-//generate submessage for user callback
-fn generate_true_randomness_submsg(
+// derive list of randomness that user requested, using randomness as seed and commitment.id as key
+fn derive_randomness(
     randomness: [u8; 32],
-    commitment: Commitment,
-    callback_limit_gas: u64,
-) -> Option<SubMsg> {
-
-    let data_request = commitment.data_request;
+    commitment: &Commitment,
+) -> Option<DerivedRandomness> {
+    let data_request = &commitment.data_request;
 
     match data_request.data_type.as_str() {
         HEX_DATA_TYPE => {
-            
             // generate list of hex randomness using PRNG algorithm base on randomness as seed and commitment.id as key
-            let hex_randomness = generate_hex_randomness(
-                randomness, commitment.id, 
+            Some(DerivedRandomness::Hex(generate_hex_randomness(
+                randomness, commitment.id.clone(), 
                 data_request.num
-            );
-
-            let sub_msg = SubMsg {
-                msg: WasmMsg::Execute {
-                    contract_addr: commitment.owner.to_string(),
-                    msg: to_binary(&CallbackExecuteMsg::ReceiveHexRandomness{ 
-                        request_id: commitment.request_id, 
-                        randomness: hex_randomness,
-                    }).unwrap(),
-                    funds: vec![],
-                }
-                .into(),
-                id: COMMITMENT_CALLBACK_REPLY_ID,
-                gas_limit: Some(callback_limit_gas),
-                reply_on: ReplyOn::Always,
-            };
-        
-            Some(sub_msg)
+            )))
         },
         INT_DATA_TYPE => {
-
-            // generate list of hex randomness using PRNG algorithm base on randomness as seed and commitment.id as key
-            let int_randomness = generate_int_randomness(
-                randomness, commitment.id, 
+            // generate list of int randomness using PRNG algorithm base on randomness as seed and commitment.id as key
+            Some(DerivedRandomness::Int(generate_int_randomness(
+                randomness, commitment.id.clone(), 
                 data_request.min, 
                 data_request.max, 
                 data_request.num
-            );
-            
-            let sub_msg = SubMsg {
-                msg: WasmMsg::Execute {
-                    contract_addr: commitment.owner.to_string(),
-                    msg: to_binary(&CallbackExecuteMsg::ReceiveIntRandomness{ 
-                        request_id: commitment.request_id, 
-                        randomness: int_randomness
-                    }).unwrap(),
-                    funds: vec![],
-                }
-                .into(),
-                id: COMMITMENT_CALLBACK_REPLY_ID,
-                gas_limit: Some(callback_limit_gas),
-                reply_on: ReplyOn::Always,
-            };
-        
-            Some(sub_msg)
+            )))
         },
         _ => None,
     }
 }
 
+// keep track of callback until its reply arrives, return reply id of the callback submessage
+// every callback has its own reply id, so replies of nested submessages (e.g. a callback that retries another one) can not be mixed up
+fn track_callback_delivery(
+    storage: &mut dyn Storage,
+    commitment_id: String,
+    randomness: HexBinary,
+) -> StdResult<u64> {
+    let count = CALLBACK_DELIVERY_COUNT.may_load(storage)?.unwrap_or_default();
+    CALLBACK_DELIVERY_COUNT.save(storage, &(count + 1))?;
+
+    let reply_id = COMMITMENT_CALLBACK_REPLY_ID_START + count;
+    CALLBACK_DELIVERIES.save(storage, reply_id, &CallbackDelivery {
+        commitment_id,
+        randomness,
+    })?;
+
+    Ok(reply_id)
+}
+
+// generate submessage that delivers derived randomness to user contract
+fn generate_callback_submsg(
+    commitment: &Commitment,
+    randomness: DerivedRandomness,
+    callback_limit_gas: u64,
+    reply_id: u64,
+) -> StdResult<SubMsg> {
+    let callback_msg = match randomness {
+        DerivedRandomness::Hex(randomness) => CallbackExecuteMsg::ReceiveHexRandomness{ 
+            request_id: commitment.request_id.clone(), 
+            randomness,
+            payload: commitment.callback_payload.clone(),
+        },
+        DerivedRandomness::Int(randomness) => CallbackExecuteMsg::ReceiveIntRandomness{ 
+            request_id: commitment.request_id.clone(), 
+            randomness,
+            payload: commitment.callback_payload.clone(),
+        },
+    };
+
+    Ok(SubMsg {
+        msg: WasmMsg::Execute {
+            contract_addr: commitment.callback_address.to_string(),
+            msg: to_binary(&callback_msg)?,
+            funds: vec![],
+        }
+        .into(),
+        id: reply_id,
+        gas_limit: Some(callback_limit_gas),
+        reply_on: ReplyOn::Always,
+    })
+}
+
+// deliver derived randomness to user, by callback submessage or by keeping it in state for pull mode
+// return false if randomness can not be derived for this commitment
+fn deliver_randomness(
+    storage: &mut dyn Storage,
+    randomness: [u8; 32],
+    commitment: &Commitment,
+    messages: &mut Vec<SubMsg>,
+) -> StdResult<bool> {
+    let derived_randomness = match derive_randomness(randomness, commitment) {
+        Some(derived_randomness) => derived_randomness,
+        None => return Ok(false),
+    };
+
+    match commitment.delivery_mode {
+        DeliveryMode::Callback => {
+            let reply_id = track_callback_delivery(storage, commitment.id.clone(), HexBinary::from(&randomness))?;
+
+            messages.push(generate_callback_submsg(commitment, derived_randomness, commitment.callback_gas_limit, reply_id)?);
+        },
+        DeliveryMode::Pull => {
+            RANDOMNESS_RESULTS.save(storage, commitment.id.clone(), &derived_randomness)?;
+        },
+    }
+
+    Ok(true)
+}
+
 fn execute_add_randomness(
     _deps: DepsMut, 
+    _env: Env,
     _info: MessageInfo, 
     random_value: String, 
     signature: String
 ) -> Result<Response, ContractError> {
+    let pause_status = PAUSE_STATUS.may_load(_deps.storage)?.unwrap_or_default();
+    assert_not_paused(pause_status.random_org, "random_org")?;
+
     if !BOTS.has(_deps.storage, _info.sender.clone()) {
         return Err(ContractError::UnregisteredAddress{});
     }
 
-    // verify random value
-    if !verify_message(random_value.clone(), signature.clone())? {
+    // convert string to random value obj, nothing in it is trusted until signature is verified
+    let org_randomness = decode_randomorg_data(random_value.clone())?;
+
+    // convert time with format "D:M:Y s:m:hZ" to Timestamp
+    let completion_time: Timestamp = convert_datetime_string(org_randomness.completionTime.clone())?;
+
+    // verify random value against random org keys that were valid when it was generated
+    let verification_keys = get_verification_keys_at(_deps.storage, completion_time)?;
+    if verification_keys.is_empty() {
+        return Err(ContractError::VerificationKeyNotFound{});
+    }
+    let mut verified = false;
+    for key in verification_keys.iter() {
+        if verify_message(random_value.clone(), signature.clone(), &key.modulus, &key.exponent)? {
+            verified = true;
+            break;
+        }
+    }
+    if !verified {
         return Err(ContractError::RSAVerificationFail{});
     }
 
+    // only data array is used as randomness, so request parameters must guarantee 32 full range bytes
+    let reject_developer_license = REJECT_DEVELOPER_LICENSE.may_load(_deps.storage)?.unwrap_or_default();
+    validate_randomorg_data(&org_randomness, reject_developer_license)?;
+
     let configs = CONFIGS.load(_deps.storage)?;
-    let bounty_denom: String = configs.bounty_denom;
-    let commit_bounty: Uint128 = configs.fee;
 
     let bot = BOTS.load(_deps.storage, _info.sender.clone())?;
 
-    // convert string to random value obj
-    let org_randomness = decode_randomorg_data(random_value.clone())?;
+    if bot.status != BotStatus::Active {
+        return Err(ContractError::BotNotActive{});
+    }
+
+    // bot whose stake has been unbonded or slashed below min stake can not add randomness
+    let stake = BOT_STAKES.may_load(_deps.storage, _info.sender.clone())?.unwrap_or_default();
+    if stake.bonded < STAKING_CONFIGS.load(_deps.storage)?.min_stake {
+        return Err(ContractError::InsufficientStake{});
+    }
 
-    // check if bot api key equivalent to api key use for generate random value
-    if !org_randomness.hashedApiKey.eq(&bot.hashed_api_key) {
+    // check if api key use for generate random value is one of bot keys, retired keys are accepted during grace period
+    if !bot.api_keys.iter().any(|api_key| api_key.hashed_api_key.eq(&org_randomness.hashedApiKey) && api_key.is_valid(_env.block.time)) {
         return Err(ContractError::InvalidApiKey{});
     }
 
 
-    // convert time with format "D:M:Y s:m:hZ" to Timestamp
-    let completion_time: Timestamp = convert_datetime_string(org_randomness.completionTime)?;
+    // serial number of each api key is increasing, reject payloads that are reused or older than the last accepted one
+    let last_serial_number = SERIAL_NUMBERS.may_load(_deps.storage, org_randomness.hashedApiKey.clone())?;
+    if let Some(last_serial_number) = last_serial_number {
+        if org_randomness.serialNumber <= last_serial_number {
+            return Err(ContractError::InvalidSerialNumber{});
+        }
+    }
+
+    // randomness generated before the oldest pending commitment may be known by bot before that commitment is made,
+    // it fulfils nothing, so it is rejected with the same boundary as `get_commitments`.
+    // bot is not slashed automatically, an honest bot may have raced nois or fetched randomness too early,
+    // owner punishes proven misbehaviour by `SlashBot`
+    if let Some(oldest_commitment) = get_oldest_pending_commitment(_deps.storage)? {
+        if completion_time.le(&oldest_commitment.commit_time) {
+            return Err(ContractError::InvalidCompletionTime{});
+        }
+    }
+
+    SERIAL_NUMBERS.save(_deps.storage, org_randomness.hashedApiKey, &org_randomness.serialNumber)?;
 
     // get commitments that satisfy time conditions 
     //      commit_time <= completion_time <= expired_time
-    let commitments = get_commitments(_deps.storage, completion_time, configs.max_callback)?;
+    let commitments = get_commitments(_deps.storage, completion_time, _env.block.time, configs.max_callback)?;
 
     let mut total_bounty = Uint128::from(0u128);
+    let mut fulfilled: u64 = 0;
     let mut messages: Vec<SubMsg> = Vec::new();
+    let mut events: Vec<Event> = Vec::new();
     
-    // generate callback message for each selected commitment
+    // deliver randomness for each selected commitment
     for commitment in commitments.iter() {
-        let wasm_msg = generate_true_randomness_submsg(
+        let delivered = deliver_randomness(
+            _deps.storage,
             org_randomness.data, 
-            (*commitment).clone(), 
-            configs.callback_limit_gas
-        );
-
-        if wasm_msg.is_some() {
-            total_bounty = total_bounty.checked_add(commit_bounty)
+            commitment, 
+            &mut messages,
+        )?;
+
+        if delivered {
+            let (protocol_fee, bounty) = split_fee(commitment.fee, configs.protocol_fee_bps);
+            credit_protocol_fees(_deps.storage, protocol_fee)?;
+            total_bounty = total_bounty.checked_add(bounty)
                 .map_err(|_| ContractError::Uint128Overflow{})?;
-            messages.push(wasm_msg.unwrap());
-        } 
+            fulfilled += 1;
+            events.push(commitment_fulfilled_event(commitment));
+        } else {
+            credit_protocol_fees(_deps.storage, commitment.fee)?;
+        }
     }
 
-    // create message to send bounty to bot for all success commitments
+    // accrue bounty of all success commitments to bot, it is paid out by claim rewards
     if !total_bounty.is_zero() {
-        messages.push(SubMsg::new(BankMsg::Send {
-            to_address: _info.sender.to_string(),
-            amount: coins(total_bounty.into(), bounty_denom),
-        }));
+        credit_bot_rewards(_deps.storage, &_info.sender, total_bounty)?;
     }
+    record_submission(_deps.storage, &_info.sender, fulfilled, total_bounty, _env.block.time)?;
     
     Ok(Response::new().add_attribute("action","add_randomness")
                 .add_attribute("random_value", random_value)
                 .add_attribute("signature", signature)
                 .add_attribute("bot", _info.sender)
-                .add_submessages(messages))
+                .add_attribute("reward", total_bounty)
+                .add_submessages(messages)
+                .add_events(events))
 }
 
 fn execute_nois_receive(
     _deps: DepsMut, 
+    _env: Env,
     _info: MessageInfo, 
     callback: NoisCallback
 ) -> Result<Response, ContractError> {
-    let configs = CONFIGS.load(_deps.storage)?;
     let nois_configs = NOIS_CONFIGS.load(_deps.storage)?;
 
     ensure_eq!(_info.sender.clone(), nois_configs.nois_proxy, ContractError::UnauthorizedReceive{});
 
+    let pause_status = PAUSE_STATUS.may_load(_deps.storage)?.unwrap_or_default();
+    assert_not_paused(pause_status.nois, "nois")?;
+
     let job_id = callback.job_id;
     let randomness: [u8; 32] = callback
         .randomness
@@ -608,40 +2067,257 @@ fn execute_nois_receive(
         .map_err(|_| ContractError::InvalidRandomness{})?;
 
     
-    // get commitment with job_id
-    let commitment = get_commitment(_deps.storage, job_id.clone())?;
-
-    if commitment.is_none() {
-        return Ok(Response::new().add_attribute("action","nois_receive")
-                                .add_attribute("message","commitment has been made")
-                                .add_attribute("nois_proxy_address", _info.sender));
-    }   
+    // get pending commitment with job_id, commitment that has been fulfilled by random org is skipped
+    let commitment = match get_commitment(_deps.storage, job_id.clone(), _env.block.time)? {
+        Some(commitment) => commitment,
+        None => {
+            return Ok(Response::new().add_attribute("action","nois_receive")
+                                    .add_attribute("message","commitment has been made")
+                                    .add_attribute("nois_proxy_address", _info.sender));
+        }
+    };
     
     let mut sub_messages: Vec<SubMsg> = Vec::new(); 
-    // generate callback submessage to user contract using receive randomnesss
-    let wasm_msg = generate_true_randomness_submsg(
+    let mut events: Vec<Event> = Vec::new();
+    // deliver randomness to user contract using receive randomnesss
+    let delivered = deliver_randomness(
+        _deps.storage,
         randomness, 
-        commitment.unwrap(), 
-        configs.callback_limit_gas
-    );
-    
-    sub_messages.push(wasm_msg.unwrap());
+        &commitment, 
+        &mut sub_messages,
+    )?;
+    if delivered {
+        events.push(commitment_fulfilled_event(&commitment));
+    }
 
-    // send bounty to contract owner 
-    if !configs.fee.is_zero() {
-        sub_messages.push(SubMsg::new(BankMsg::Send {
-            to_address: OWNER.load(_deps.storage)?.to_string(),
-            amount: coins(configs.fee.into(), configs.bounty_denom),
-        }));
+    // owner runs nois fulfillment and takes its share as rewards, the share stays in treasury if ownership has been renounced
+    let configs = CONFIGS.load(_deps.storage)?;
+    let (protocol_fee, bounty) = split_fee(commitment.fee, configs.protocol_fee_bps);
+    credit_protocol_fees(_deps.storage, protocol_fee)?;
+    match OWNER.may_load(_deps.storage)? {
+        Some(owner) => credit_bot_rewards(_deps.storage, &owner, bounty)?,
+        None => credit_protocol_fees(_deps.storage, bounty)?,
     }
 
     Ok(Response::new().add_submessages(sub_messages)
+                .add_events(events)
                 .add_attribute("job_id", job_id)
                 .add_attribute("randomness", hex::encode(randomness))
                 .add_attribute("action","nois_receive")
                 .add_attribute("nois_proxy_address", _info.sender))
 }
 
+// report gas limit that callback of a fulfilled commitment really runs with, zero for pull mode
+fn commitment_fulfilled_event(commitment: &Commitment) -> Event {
+    Event::new("commitment_fulfilled")
+        .add_attribute("commitment_id", commitment.id.clone())
+        .add_attribute("callback_gas_limit", commitment.callback_gas_limit.to_string())
+}
+
+// divide aurand fee of a commitment into (treasury share, fulfiller share)
+fn split_fee(fee: Uint128, protocol_fee_bps: u16) -> (Uint128, Uint128) {
+    let protocol_fee = fee.multiply_ratio(protocol_fee_bps, MAX_FEE_BPS);
+    (protocol_fee, fee - protocol_fee)
+}
+
+fn execute_claim_rewards(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let rewards = take_bot_rewards(_deps.storage, &_info.sender)?;
+    if rewards.is_zero() {
+        return Err(ContractError::NoRewards{});
+    }
+
+    let configs = CONFIGS.load(_deps.storage)?;
+
+    // rewards can be sent to another address if bot address can not receive funds
+    let recipient = recipient.unwrap_or_else(|| _info.sender.clone());
+
+    Ok(Response::new().add_message(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: coins(rewards.into(), configs.bounty_denom),
+                })
+                .add_attribute("action","claim_rewards")
+                .add_attribute("amount", rewards)
+                .add_attribute("recipient", recipient)
+                .add_attribute("bot", _info.sender))
+}
+
+fn execute_withdraw(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    amount: Uint128,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    assert_role(_deps.storage, &_info.sender, Role::Treasurer)?;
+
+    // only collected fees can be withdrawn, reserved funds and bot rewards are never touched
+    let mut treasury = TREASURY.may_load(_deps.storage)?.unwrap_or_default();
+    if amount.is_zero() || amount > treasury.protocol_fees {
+        return Err(ContractError::InsufficientTreasury{});
+    }
+    treasury.protocol_fees -= amount;
+    TREASURY.save(_deps.storage, &treasury)?;
+
+    let configs = CONFIGS.load(_deps.storage)?;
+
+    Ok(Response::new().add_message(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: coins(amount.into(), configs.bounty_denom),
+                })
+                .add_attribute("action","withdraw")
+                .add_attribute("amount", amount)
+                .add_attribute("recipient", recipient)
+                .add_attribute("treasurer", _info.sender))
+}
+
+fn execute_reclaim_expired(
+    _deps: DepsMut, 
+    _env: Env,
+    _info: MessageInfo, 
+    commitment_ids: Vec<String>
+) -> Result<Response, ContractError> {
+    let configs = CONFIGS.load(_deps.storage)?;
+
+    let mut total_keeper_reward = Uint128::from(0u128);
+    let mut messages: Vec<SubMsg> = Vec::new();
+    let mut events: Vec<Event> = Vec::new();
+
+    for commit_id in commitment_ids {
+        let status = COMMITMENT_STATUS.may_load(_deps.storage, commit_id.clone())?
+            .ok_or(ContractError::CommitmentNotFound{})?;
+
+        // commitment has been fulfilled or refunded by an earlier call
+        if status != CommitmentStatus::Pending {
+            continue;
+        }
+
+        let commitment = PENDING_COMMITMENTS.load(_deps.storage, commit_id.clone())?;
+        if commitment.expired_time.ge(&_env.block.time) {
+            return Err(ContractError::CommitmentNotExpired{});
+        }
+
+        settle_commitment(_deps.storage, commit_id.clone(), CommitmentStatus::Refunded { time: _env.block.time })?;
+
+        // keeper who reclaims someone else's commitment takes a reward from its aurand fee
+        let keeper_reward = if _info.sender.eq(&commitment.owner) {
+            Uint128::from(0u128)
+        } else {
+            configs.keeper_reward.min(commitment.fee)
+        };
+        let refund = commitment.fee - keeper_reward;
+        total_keeper_reward = total_keeper_reward.checked_add(keeper_reward)
+            .map_err(|_| ContractError::Uint128Overflow{})?;
+
+        if !refund.is_zero() {
+            messages.push(SubMsg::new(BankMsg::Send {
+                to_address: commitment.owner.to_string(),
+                amount: coins(refund.into(), configs.bounty_denom.clone()),
+            }));
+        }
+
+        // notify user contract, failure of this callback does not revert the refund
+        if commitment.delivery_mode == DeliveryMode::Callback {
+            messages.push(SubMsg {
+                msg: WasmMsg::Execute {
+                    contract_addr: commitment.callback_address.to_string(),
+                    msg: to_binary(&CallbackExecuteMsg::ReceiveRandomnessExpired{ 
+                        request_id: commitment.request_id.clone(), 
+                        payload: commitment.callback_payload.clone(),
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+                id: EXPIRED_CALLBACK_REPLY_ID,
+                gas_limit: Some(commitment.callback_gas_limit),
+                reply_on: ReplyOn::Error,
+            });
+        }
+
+        events.push(Event::new("commitment_expired")
+            .add_attribute("commitment_id", commit_id)
+            .add_attribute("request_id", commitment.request_id)
+            .add_attribute("owner", commitment.owner)
+            .add_attribute("refund", refund)
+            .add_attribute("keeper_reward", keeper_reward)
+            .add_attribute("callback_gas_limit", commitment.callback_gas_limit.to_string()));
+    }
+
+    // send keeper reward for all reclaimed commitments
+    if !total_keeper_reward.is_zero() {
+        messages.push(SubMsg::new(BankMsg::Send {
+            to_address: _info.sender.to_string(),
+            amount: coins(total_keeper_reward.into(), configs.bounty_denom),
+        }));
+    }
+
+    Ok(Response::new().add_submessages(messages)
+                .add_events(events)
+                .add_attribute("action","reclaim_expired")
+                .add_attribute("keeper_reward", total_keeper_reward)
+                .add_attribute("keeper", _info.sender))
+}
+
+fn execute_retry_callback(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    commitment_id: String,
+    gas_limit: u64,
+) -> Result<Response, ContractError> {
+    let commitment = COMMITMENT_RECORDS.may_load(_deps.storage, commitment_id.clone())?
+        .ok_or(ContractError::CommitmentNotFound{})?;
+
+    if !commitment.owner.eq(&_info.sender) {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    let (source, time) = match COMMITMENT_STATUS.load(_deps.storage, commitment_id.clone())? {
+        CommitmentStatus::CallbackFailed { source, time } => (source, time),
+        _ => return Err(ContractError::CallbackNotFailed{}),
+    };
+
+    // move commitment back to fulfilled, a new failure of this callback will be recorded again by reply
+    let failed_callback = FAILED_CALLBACKS.load(_deps.storage, commitment_id.clone())?;
+    FAILED_CALLBACKS.remove(_deps.storage, commitment_id.clone());
+    COMMITMENT_STATUS.save(_deps.storage, commitment_id.clone(), &CommitmentStatus::Fulfilled { source, time })?;
+
+    let reply_id = track_callback_delivery(_deps.storage, commitment_id.clone(), failed_callback.seed)?;
+
+    let sub_msg = generate_callback_submsg(&commitment, failed_callback.randomness, gas_limit, reply_id)?;
+
+    Ok(Response::new().add_submessage(sub_msg)
+                .add_attribute("action","retry_callback")
+                .add_attribute("commitment_id", commitment_id)
+                .add_attribute("gas_limit", gas_limit.to_string())
+                .add_attribute("owner", _info.sender))
+}
+
+fn execute_claim_randomness(
+    _deps: DepsMut, 
+    _info: MessageInfo, 
+    commitment_id: String,
+) -> Result<Response, ContractError> {
+    let commitment = COMMITMENT_RECORDS.may_load(_deps.storage, commitment_id.clone())?
+        .ok_or(ContractError::CommitmentNotFound{})?;
+
+    if !commitment.owner.eq(&_info.sender) {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    // randomness is claimed once, it is removed from state and returned in response data
+    let randomness = RANDOMNESS_RESULTS.may_load(_deps.storage, commitment_id.clone())?
+        .ok_or(ContractError::RandomnessNotFound{})?;
+    RANDOMNESS_RESULTS.remove(_deps.storage, commitment_id.clone());
+
+    Ok(Response::new().set_data(to_binary(&randomness)?)
+                .add_attribute("action","claim_randomness")
+                .add_attribute("commitment_id", commitment_id)
+                .add_attribute("request_id", commitment.request_id)
+                .add_attribute("owner", _info.sender))
+}
+
 /// Handling contract query
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
@@ -651,13 +2327,142 @@ pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetNumberOfCommitment{} => to_binary(&query_get_number_of_commitments(_deps)?),
         QueryMsg::GetBotInfo{address} => to_binary(&query_bot_info(_deps,address)?),
         QueryMsg::GetConfigs{} => to_binary(&query_configs(_deps)?),
+        QueryMsg::GetOwnership{} => to_binary(&query_ownership(_deps)?),
+        QueryMsg::GetScheduledConfigChanges{start_after, limit} => to_binary(&query_scheduled_config_changes(_deps, start_after, limit)?),
+        QueryMsg::GetBotTombstone{address} => to_binary(&BOT_TOMBSTONES.may_load(_deps.storage, _deps.api.addr_validate(&address)?)?),
+        QueryMsg::ListBots{status, start_after, limit} => to_binary(&query_list_bots(_deps, status, start_after, limit)?),
+        QueryMsg::GetBotRewards{address} => to_binary(&query_bot_rewards(_deps, address)?),
+        QueryMsg::ListVerificationKeys{start_after, limit} => to_binary(&query_list_verification_keys(_deps, start_after, limit)?),
+        QueryMsg::GetTreasury{} => to_binary(&query_treasury(_deps, _env)?),
+        QueryMsg::GetPauseStatus{} => to_binary(&PAUSE_STATUS.may_load(_deps.storage)?.unwrap_or_default()),
+        QueryMsg::HasRole{role, address} => to_binary(&query_has_role(_deps, role, address)?),
+        QueryMsg::ListRoleMembers{role, start_after, limit} => to_binary(&query_list_role_members(_deps, role, start_after, limit)?),
+        QueryMsg::GetCommitment{id} => to_binary(&query_commitment(_deps, _env, id)?),
+        QueryMsg::GetCommitmentByRequest{owner, request_id} => to_binary(&query_commitment_by_request(_deps, _env, owner, request_id)?),
+        QueryMsg::GetFailedCallback{commitment_id} => to_binary(&FAILED_CALLBACKS.may_load(_deps.storage, commitment_id)?),
+        QueryMsg::GetRandomnessResult{commitment_id} => to_binary(&RANDOMNESS_RESULTS.may_load(_deps.storage, commitment_id)?),
+        QueryMsg::GetRequestPrice{data_type, num, delivery_mode, callback_gas_limit} => to_binary(&query_request_price(_deps, data_type, num, delivery_mode, callback_gas_limit)?),
     }
 }
 
+pub fn query_request_price(
+    _deps: Deps,
+    data_type: String,
+    num: u32,
+    delivery_mode: Option<DeliveryMode>,
+    callback_gas_limit: Option<u64>,
+) -> StdResult<RequestPriceQuery> {
+    let price = calculate_request_price(
+        _deps.storage,
+        &DataRequest {data_type, min: 0, max: 0, num},
+        &delivery_mode.unwrap_or(DeliveryMode::Callback),
+        callback_gas_limit,
+    ).map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(RequestPriceQuery{
+        funds: coins(price.total_fee.into(), price.denom),
+        fee: price.fee,
+        nois_fee: price.nois_fee,
+        callback_gas_limit: price.callback_gas_limit,
+    })
+}
+
+pub fn query_ownership(_deps: Deps) -> StdResult<OwnershipQuery> {
+    let owner = OWNER.may_load(_deps.storage)?;
+    let pending_owner = PENDING_OWNER.may_load(_deps.storage)?;
+
+    Ok(OwnershipQuery{
+        owner: owner.map(|owner| owner.to_string()),
+        pending_owner: pending_owner.clone().map(|pending_owner| pending_owner.address.to_string()),
+        pending_expires_at: pending_owner.and_then(|pending_owner| pending_owner.expires_at),
+    })
+}
+
+pub fn query_has_role(_deps: Deps, role: Role, address: String) -> StdResult<bool> {
+    let address = _deps.api.addr_validate(&address)?;
+    has_role(_deps.storage, &role, &address)
+}
+
+pub fn query_list_role_members(_deps: Deps, role: Role, start_after: Option<String>, limit: Option<u32>) -> StdResult<RoleMembersQuery> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|addr| _deps.api.addr_validate(&addr))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    let members = ROLES
+        .prefix(role.as_str().to_string())
+        .keys(_deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|addr| addr.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(RoleMembersQuery{members})
+}
+
+pub fn query_list_verification_keys(_deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<VerificationKeysQuery> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let keys = VERIFICATION_KEYS
+        .range(_deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, key)| key))
+        .collect::<StdResult<Vec<VerificationKey>>>()?;
+
+    Ok(VerificationKeysQuery{keys})
+}
+
+pub fn query_scheduled_config_changes(_deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<ScheduledConfigChangesQuery> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let changes = SCHEDULED_CONFIG_CHANGES
+        .range(_deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, scheduled)| scheduled))
+        .collect::<StdResult<Vec<ScheduledConfigChange>>>()?;
+
+    Ok(ScheduledConfigChangesQuery{changes})
+}
+
+pub fn query_bot_rewards(_deps: Deps, address: String) -> StdResult<BotRewardsQuery> {
+    let addr = _deps.api.addr_validate(&address)?;
+    let configs = CONFIGS.load(_deps.storage)?;
+
+    Ok(BotRewardsQuery{
+        address: addr.to_string(),
+        denom: configs.bounty_denom,
+        amount: BOT_REWARDS.may_load(_deps.storage, addr)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_treasury(_deps: Deps, _env: Env) -> StdResult<TreasuryQuery> {
+    let configs = CONFIGS.load(_deps.storage)?;
+    let treasury = TREASURY.may_load(_deps.storage)?.unwrap_or_default();
+    let balance = _deps.querier.query_balance(_env.contract.address, configs.bounty_denom.clone())?.amount;
+
+    // funds that are not tracked, e.g. sent to contract directly
+    let accounted = treasury.protocol_fees + treasury.bot_rewards + treasury.reserved + treasury.staked;
+
+    Ok(TreasuryQuery{
+        denom: configs.bounty_denom,
+        protocol_fees: treasury.protocol_fees,
+        bot_rewards: treasury.bot_rewards,
+        reserved: treasury.reserved,
+        staked: treasury.staked,
+        balance,
+        unaccounted: balance.saturating_sub(accounted),
+    })
+}
+
 pub fn query_configs(_deps: Deps) -> StdResult<ConfigsQuery> {
     let configs = CONFIGS.load(_deps.storage)?;
     let nois_configs = NOIS_CONFIGS.load(_deps.storage)?;
     let time_configs = TIME_CONFIGS.load(_deps.storage)?;
+    let gas_configs = GAS_CONFIGS.load(_deps.storage)?;
+    let staking_configs = STAKING_CONFIGS.load(_deps.storage)?;
+    let api_key_policy = API_KEY_POLICY.load(_deps.storage)?;
 
     Ok(ConfigsQuery{
         nois_proxy: nois_configs.nois_proxy.into(),
@@ -665,8 +2470,20 @@ pub fn query_configs(_deps: Deps) -> StdResult<ConfigsQuery> {
         bounty_denom: configs.bounty_denom,
         fee: configs.fee,
         callback_limit_gas: configs.callback_limit_gas,
+        keeper_reward: configs.keeper_reward,
+        protocol_fee_bps: configs.protocol_fee_bps,
         time_expired: time_configs.time_expired,
         time_per_block: time_configs.time_per_block,
+        max_callback_gas: gas_configs.max_callback_gas,
+        callback_gas_price: gas_configs.callback_gas_price,
+        config_change_delay: CONFIG_CHANGE_DELAY.may_load(_deps.storage)?.unwrap_or_default(),
+        min_stake: staking_configs.min_stake,
+        unbonding_period: staking_configs.unbonding_period,
+        registration_mode: REGISTRATION_MODE.may_load(_deps.storage)?.unwrap_or_default(),
+        api_key_update_cooldown: api_key_policy.update_cooldown,
+        api_key_grace_period: api_key_policy.grace_period,
+        max_api_keys: api_key_policy.max_api_keys,
+        reject_developer_license: REJECT_DEVELOPER_LICENSE.may_load(_deps.storage)?.unwrap_or_default(),
     })
 }
 
@@ -679,12 +2496,48 @@ pub fn query_bot_info(_deps: Deps, address: String) -> StdResult<Option<BotInfoQ
 
     let bot = BOTS.load(_deps.storage, addr).unwrap();
 
-    Ok(Some(BotInfoQuery{
+    Ok(Some(bot_info(_deps.storage, bot)?))
+}
+
+fn bot_info(storage: &dyn Storage, bot: Bot) -> StdResult<BotInfoQuery> {
+    let stake = BOT_STAKES.may_load(storage, bot.address.clone())?.unwrap_or_default();
+
+    Ok(BotInfoQuery{
         address: bot.address.to_string(),
-        hashed_api_key: bot.hashed_api_key,
+        api_keys: bot.api_keys,
         moniker: bot.moniker,
         last_update: bot.last_update,
-    }))
+        status: bot.status,
+        bonded: stake.bonded,
+        unbonding: stake.unbonding,
+        slashes: stake.slashes,
+        metrics: BOT_METRICS.may_load(storage, bot.address)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_list_bots(_deps: Deps, status: Option<BotStatus>, start_after: Option<String>, limit: Option<u32>) -> StdResult<BotsQuery> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|addr| _deps.api.addr_validate(&addr))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    // status index is used to keep filtered pages bounded by limit
+    let bots = match status {
+        Some(status) => BOTS_BY_STATUS
+            .prefix(status.as_str().to_string())
+            .keys(_deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|addr| addr.and_then(|addr| bot_info(_deps.storage, BOTS.load(_deps.storage, addr)?)))
+            .collect::<StdResult<Vec<BotInfoQuery>>>()?,
+        None => BOTS
+            .range(_deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.and_then(|(_, bot)| bot_info(_deps.storage, bot)))
+            .collect::<StdResult<Vec<BotInfoQuery>>>()?,
+    };
+
+    Ok(BotsQuery{bots})
 }
 
 pub fn query_get_pending_commitments(_deps: Deps, limit: u32) -> StdResult<PendingCommitmentsQuery> {
@@ -708,14 +2561,39 @@ pub fn query_get_commitments(_deps: Deps, limit: u32) -> StdResult<CommitmentsQu
     // we limit number of commitments can take per query to prevent out of gas
     for i in 0..COMMITMENTS.iter(_deps.storage)?.take(limit as usize).count() {
         let commitment = COMMITMENTS.get(_deps.storage,i.try_into().unwrap())?;
-        if commitment.is_some() {
-            vecs.push(commitment.unwrap());
+        if let Some(commitment) = commitment {
+            vecs.push(commitment);
         }
     }
 
     Ok(CommitmentsQuery{commitments:vecs})
 }
 
+pub fn query_commitment(_deps: Deps, _env: Env, id: String) -> StdResult<Option<CommitmentQuery>> {
+    let commitment = match COMMITMENT_RECORDS.may_load(_deps.storage, id.clone())? {
+        Some(commitment) => commitment,
+        None => return Ok(None),
+    };
+
+    let mut status = COMMITMENT_STATUS.load(_deps.storage, id)?;
+
+    // pending commitment that has passed its expiration time can be reclaimed by its owner
+    if status == CommitmentStatus::Pending && commitment.expired_time.lt(&_env.block.time) {
+        status = CommitmentStatus::Expired;
+    }
+
+    Ok(Some(CommitmentQuery{commitment, status}))
+}
+
+pub fn query_commitment_by_request(_deps: Deps, _env: Env, owner: String, request_id: String) -> StdResult<Option<CommitmentQuery>> {
+    let owner = _deps.api.addr_validate(&owner)?;
+
+    match REQUEST_COMMITMENTS.may_load(_deps.storage, (owner, request_id))? {
+        Some(id) => query_commitment(_deps, _env, id),
+        None => Ok(None),
+    }
+}
+
 pub fn query_get_number_of_commitments(deps: Deps) -> StdResult<NumberOfCommitmentQuery> {
     let count: u32 = COMMITMENTS.len(deps.storage)?;
     Ok(NumberOfCommitmentQuery{num: count})
@@ -723,5 +2601,45 @@ pub fn query_get_number_of_commitments(deps: Deps) -> StdResult<NumberOfCommitme
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(_deps: DepsMut, _env: Env, _msg: Reply) -> Result<Response, ContractError> {
-    Ok(Response::new())
+    match _msg.id {
+        id if id >= COMMITMENT_CALLBACK_REPLY_ID_START => reply_commitment_callback(_deps, _env, id, _msg.result),
+        _ => Ok(Response::new()),
+    }
+}
+
+// reply id of callback submessage tells which tracked delivery this reply belongs to
+fn reply_commitment_callback(_deps: DepsMut, _env: Env, reply_id: u64, result: SubMsgResult) -> Result<Response, ContractError> {
+    let delivery = CALLBACK_DELIVERIES.may_load(_deps.storage, reply_id)?
+        .ok_or(ContractError::CustomError{val: String::from("Unknown callback reply!")})?;
+    CALLBACK_DELIVERIES.remove(_deps.storage, reply_id);
+
+    let error = match result {
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+        SubMsgResult::Err(error) => error,
+    };
+
+    let commitment_id = delivery.commitment_id;
+    let commitment = COMMITMENT_RECORDS.load(_deps.storage, commitment_id.clone())?;
+
+    let (source, time) = match COMMITMENT_STATUS.load(_deps.storage, commitment_id.clone())? {
+        CommitmentStatus::Fulfilled { source, time } => (source, time),
+        _ => return Err(ContractError::CustomError{val: String::from("Callback reply for unfulfilled commitment!")}),
+    };
+
+    let seed: [u8; 32] = delivery.randomness.to_array()?;
+    let randomness = derive_randomness(seed, &commitment)
+        .ok_or(ContractError::CustomError{val: String::from("Invalid data type!")})?;
+
+    // keep derived randomness so that owner can retry the callback later
+    FAILED_CALLBACKS.save(_deps.storage, commitment_id.clone(), &FailedCallback {
+        error: error.clone(),
+        randomness,
+        seed: delivery.randomness,
+        time: _env.block.time,
+    })?;
+    COMMITMENT_STATUS.save(_deps.storage, commitment_id.clone(), &CommitmentStatus::CallbackFailed { source, time })?;
+
+    Ok(Response::new().add_attribute("action","callback_failed")
+                .add_attribute("commitment_id", commitment_id)
+                .add_attribute("error", error))
 }
@@ -1,19 +1,27 @@
 #[cfg(test)]
 mod unit_tests {
-    use crate::contract::{instantiate, execute, query, reply};
+    use crate::contract::{instantiate, execute, query, reply, migrate};
+    use crate::migrations::v0_1_0;
+    use cw2::{set_contract_version, get_contract_version};
     use nois::{NoisCallback, ProxyExecuteMsg};
 
     use crate::error::ContractError;
     use crate::msg::{
-        ExecuteMsg, InstantiateMsg, CallbackExecuteMsg, QueryMsg,
+        ExecuteMsg, InstantiateMsg, MigrateMsg, CallbackExecuteMsg, QueryMsg,
         PendingCommitmentsQuery, BotInfoQuery, CommitmentsQuery, 
-        NumberOfCommitmentQuery, ConfigsQuery,
+        NumberOfCommitmentQuery, ConfigsQuery, CommitmentQuery, RequestPriceQuery,
+        OwnershipQuery, RoleMembersQuery, ScheduledConfigChangesQuery, TreasuryQuery,
+        BotRewardsQuery, BotsQuery, VerificationKeysQuery,
     };
     use crate::utils::{
         make_commit_id,
     };
     use crate::state::{
-        COMMITMENTS, Commitment, PENDING_COMMITMENTS, DataRequest
+        Commitment, DataRequest, add_commitment, CommitmentStatus, RandomnessSource,
+        FailedCallback, DerivedRandomness, DeliveryMode,
+        NOIS_CONFIGS, NoisConfigs, TIME_CONFIGS, TimeConfigs, OWNER, Role, PauseStatus,
+        Configs, ConfigChange, ScheduledConfigChange, Unbonding, Slash,
+        BotStatus, RegistrationMode, BotMetrics, ApiKey, BOTS, BotTombstone, VerificationKey,
     };
 
     use cosmwasm_std::testing::{
@@ -22,11 +30,12 @@ mod unit_tests {
     use cosmwasm_std::{
         Uint128, OwnedDeps, Env, Response,BlockInfo, ContractInfo, Timestamp, 
         Addr, SubMsg, Coin, coins, to_binary, WasmMsg, ReplyOn, HexBinary, BankMsg,
-        SubMsgResult,
+        SubMsgResult, Event, Decimal, from_binary,
     };
 
     const NOIS_CALLBACK_REPLY_ID: u64 = 1;
-    const COMMITMENT_CALLBACK_REPLY_ID: u64 = 2;
+    const COMMITMENT_CALLBACK_REPLY_ID_START: u64 = 1000;
+    const EXPIRED_CALLBACK_REPLY_ID: u64 = 3;
 
     const CONTRACT_ADDR: &str = "contract";
     const CREATOR: &str = "creator";
@@ -40,7 +49,21 @@ mod unit_tests {
     const FEE: u128 = 300u128;
     const NOIS_FEE: u128 = 300u128;
     const CALLBACK_LIMIT_GAS: u64 = 1500000u64; 
+    const MAX_CALLBACK_GAS: u64 = 2000000u64;
+    const CONFIG_CHANGE_DELAY: u64 = 0u64;
     const MAX_CALLBACK: u32 = 5u32;
+    const KEEPER_REWARD: u128 = 50u128;
+    const PROTOCOL_FEE_BPS: u16 = 2000u16;
+    const PROTOCOL_FEE: u128 = 60u128; // 20% of FEE
+    const MIN_STAKE: u128 = 1000u128;
+    const UNBONDING_PERIOD: u64 = 100u64;
+    const API_KEY_UPDATE_COOLDOWN: u64 = 10u64;
+    const API_KEY_GRACE_PERIOD: u64 = 10u64;
+    const MAX_API_KEYS: u32 = 3u32;
+    const KEEPER: &str = "keeper";
+    const GAME: &str = "game";
+    const NEW_OWNER: &str = "new owner";
+    const OPERATOR: &str = "operator";
 
     fn default_setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
         let mut deps = mock_dependencies();
@@ -53,12 +76,24 @@ mod unit_tests {
             nois_fee: Uint128::from(NOIS_FEE),
             callback_limit_gas: CALLBACK_LIMIT_GAS,
             max_callback: MAX_CALLBACK,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+            max_callback_gas: MAX_CALLBACK_GAS,
+            callback_gas_price: Decimal::zero(),
+            config_change_delay: CONFIG_CHANGE_DELAY,
+            min_stake: Uint128::from(MIN_STAKE),
+            unbonding_period: UNBONDING_PERIOD,
+            registration_mode: RegistrationMode::Open,
+            api_key_update_cooldown: API_KEY_UPDATE_COOLDOWN,
+            api_key_grace_period: API_KEY_GRACE_PERIOD,
+            max_api_keys: MAX_API_KEYS,
+            reject_developer_license: false,
         };
         let info = mock_info(CREATOR, &[]);
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
         
-        return deps;
+        deps
     }
 
     // EXECUTE
@@ -77,6 +112,18 @@ mod unit_tests {
             nois_fee: Uint128::from(300u128),
             callback_limit_gas: 150000,
             max_callback: MAX_CALLBACK,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+            max_callback_gas: MAX_CALLBACK_GAS,
+            callback_gas_price: Decimal::zero(),
+            config_change_delay: CONFIG_CHANGE_DELAY,
+            min_stake: Uint128::from(MIN_STAKE),
+            unbonding_period: UNBONDING_PERIOD,
+            registration_mode: RegistrationMode::Open,
+            api_key_update_cooldown: API_KEY_UPDATE_COOLDOWN,
+            api_key_grace_period: API_KEY_GRACE_PERIOD,
+            max_api_keys: MAX_API_KEYS,
+            reject_developer_license: false,
         };
         let info = mock_info(CREATOR, &[]);
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
@@ -88,6 +135,41 @@ mod unit_tests {
     }
 
 
+    #[test]
+    fn instantiate_fail_with_callback_gas_limit_exceeded() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            nois_proxy: NOIS_PROXY_ADDR.to_string(),
+            time_expired: TIME_EXPIRED,
+            time_per_block: TIME_PER_BLOCK,
+            bounty_denom: String::from(DENOM),
+            fee: Uint128::from(FEE),
+            nois_fee: Uint128::from(NOIS_FEE),
+            callback_limit_gas: MAX_CALLBACK_GAS + 1,
+            max_callback: MAX_CALLBACK,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+            max_callback_gas: MAX_CALLBACK_GAS,
+            callback_gas_price: Decimal::zero(),
+            config_change_delay: CONFIG_CHANGE_DELAY,
+            min_stake: Uint128::from(MIN_STAKE),
+            unbonding_period: UNBONDING_PERIOD,
+            registration_mode: RegistrationMode::Open,
+            api_key_update_cooldown: API_KEY_UPDATE_COOLDOWN,
+            api_key_grace_period: API_KEY_GRACE_PERIOD,
+            max_api_keys: MAX_API_KEYS,
+            reject_developer_license: false,
+        };
+        let info = mock_info(CREATOR, &[]);
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+
+        match res {
+            ContractError::CallbackGasLimitExceeded {} => {},
+            _ => panic!(),
+        };
+    }
+
+
     #[test]
     fn instantiate_works() {
         default_setup();
@@ -103,6 +185,8 @@ mod unit_tests {
             fee: Uint128::from(300u128),
             callback_limit_gas: 150000,
             max_callback: 5u32,
+            keeper_reward: Uint128::from(50u128),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
         };
 
         let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_set_configs).unwrap_err();
@@ -113,6 +197,46 @@ mod unit_tests {
     }
     
 
+    #[test]
+    fn set_configs_fail_with_invalid_fee_split() {
+        let mut deps = default_setup();
+
+        let request_set_configs = ExecuteMsg::SetConfigs{
+            bounty_denom: "ueaura".to_string(),
+            fee: Uint128::from(300u128),
+            callback_limit_gas: 150000,
+            max_callback: 5u32,
+            keeper_reward: Uint128::from(50u128),
+            protocol_fee_bps: 10001u16,
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_configs).unwrap_err();
+        match res {
+            ContractError::InvalidFeeSplit{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn set_configs_fail_with_callback_gas_limit_exceeded() {
+        let mut deps = default_setup();
+
+        let request_set_configs = ExecuteMsg::SetConfigs{
+            bounty_denom: "ueaura".to_string(),
+            fee: Uint128::from(300u128),
+            callback_limit_gas: MAX_CALLBACK_GAS + 1,
+            max_callback: 5u32,
+            keeper_reward: Uint128::from(50u128),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_configs).unwrap_err();
+        match res {
+            ContractError::CallbackGasLimitExceeded{} => {},
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn set_configs_success() {
         let mut deps = default_setup();
@@ -121,12 +245,16 @@ mod unit_tests {
         let fee = Uint128::from(300u128);
         let callback_limit_gas = 150000;
         let max_callback= 5u32;
+        let keeper_reward = Uint128::from(50u128);
+        let protocol_fee_bps = 1000u16;
 
         let request_set_configs = ExecuteMsg::SetConfigs{
             bounty_denom,
             fee,
             callback_limit_gas,
-            max_callback
+            max_callback,
+            keeper_reward,
+            protocol_fee_bps,
         };
 
         let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_configs).unwrap();
@@ -135,9 +263,202 @@ mod unit_tests {
                                     .add_attribute("fee", fee)
                                     .add_attribute("callback_limit_gas", callback_limit_gas.to_string())
                                     .add_attribute("max_callback", max_callback.to_string())
+                                    .add_attribute("keeper_reward", keeper_reward)
+                                    .add_attribute("protocol_fee_bps", protocol_fee_bps.to_string())
+                                    .add_attribute("change_id", "1")
+                                    .add_attribute("effective_time", mock_env().block.time.seconds().to_string())
                                     .add_attribute("owner",  CREATOR));
     }
 
+    /***** Config Changes *****/
+    // config change delay is zero in default setup, so new delay is applied right away
+    fn set_config_change_delay(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, delay: u64) {
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::SetConfigChangeDelay{delay}).unwrap();
+    }
+
+    #[test]
+    fn execute_config_change_success_after_delay() {
+        let mut deps = default_setup();
+        set_config_change_delay(&mut deps, 100);
+
+        let request_set_configs = ExecuteMsg::SetConfigs{
+            bounty_denom: String::from(DENOM),
+            fee: Uint128::from(500u128),
+            callback_limit_gas: CALLBACK_LIMIT_GAS,
+            max_callback: MAX_CALLBACK,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_configs).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScheduledConfigChanges{start_after: None, limit: None}).unwrap();
+        assert_eq!(res, to_binary(&ScheduledConfigChangesQuery{
+            changes: vec![ScheduledConfigChange{
+                id: 2,
+                change: ConfigChange::Configs(Configs{
+                    bounty_denom: String::from(DENOM),
+                    fee: Uint128::from(500u128),
+                    callback_limit_gas: CALLBACK_LIMIT_GAS,
+                    max_callback: MAX_CALLBACK,
+                    keeper_reward: Uint128::from(KEEPER_REWARD),
+                    protocol_fee_bps: PROTOCOL_FEE_BPS,
+                }),
+                proposer: Addr::unchecked(CREATOR),
+                scheduled_time: mock_env().block.time,
+                effective_time: mock_env().block.time.plus_seconds(100),
+                expired_time: mock_env().block.time.plus_seconds(100 + 604800),
+            }],
+        }).unwrap());
+
+        // price does not change before notice period is over
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 2}).unwrap_err();
+        match res {
+            ContractError::ConfigChangeNotDue{} => {},
+            _ => panic!(),
+        };
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap();
+        let configs: ConfigsQuery = from_binary(&res).unwrap();
+        assert_eq!(configs.fee, Uint128::from(FEE));
+        assert_eq!(configs.config_change_delay, 100);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 2}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","execute_config_change")
+                                    .add_attribute("change_id", "2")
+                                    .add_attribute("kind", "configs")
+                                    .add_attribute("executor", USER));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap();
+        let configs: ConfigsQuery = from_binary(&res).unwrap();
+        assert_eq!(configs.fee, Uint128::from(500u128));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScheduledConfigChanges{start_after: None, limit: None}).unwrap();
+        assert_eq!(res, to_binary(&ScheduledConfigChangesQuery{changes: vec![]}).unwrap());
+    }
+
+    #[test]
+    fn execute_config_change_success_without_delay() {
+        let mut deps = default_setup();
+
+        let request_set_configs = ExecuteMsg::SetConfigs{
+            bounty_denom: String::from(DENOM),
+            fee: Uint128::from(500u128),
+            callback_limit_gas: CALLBACK_LIMIT_GAS,
+            max_callback: MAX_CALLBACK,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_configs).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap();
+        let configs: ConfigsQuery = from_binary(&res).unwrap();
+        assert_eq!(configs.fee, Uint128::from(500u128));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScheduledConfigChanges{start_after: None, limit: None}).unwrap();
+        assert_eq!(res, to_binary(&ScheduledConfigChangesQuery{changes: vec![]}).unwrap());
+    }
+
+    #[test]
+    fn execute_config_change_fail_with_config_change_expired() {
+        let mut deps = default_setup();
+        set_config_change_delay(&mut deps, 100);
+
+        let request_set_nois_configs = ExecuteMsg::SetNoisConfigs{
+            nois_proxy: String::from(USER),
+            nois_fee: Uint128::from(300u128),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_nois_configs).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100 + 604800 + 1);
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 2}).unwrap_err();
+        match res {
+            ContractError::ConfigChangeExpired{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn execute_config_change_fail_with_replaced_config_change() {
+        let mut deps = default_setup();
+        set_config_change_delay(&mut deps, 100);
+
+        let request_set_nois_configs = ExecuteMsg::SetNoisConfigs{
+            nois_proxy: String::from(USER),
+            nois_fee: Uint128::from(300u128),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_nois_configs).unwrap();
+
+        // newer nois config change replaces the queued one
+        let request_set_nois_configs = ExecuteMsg::SetNoisConfigs{
+            nois_proxy: String::from(NOIS_PROXY_ADDR),
+            nois_fee: Uint128::from(400u128),
+        };
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(10);
+        execute(deps.as_mut(), env, mock_info(CREATOR, &[]), request_set_nois_configs).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(110);
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 3}).unwrap();
+
+        // older change can not revert the newer one
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 2}).unwrap_err();
+        match res {
+            ContractError::ConfigChangeNotFound{} => {},
+            _ => panic!(),
+        };
+
+        let res: ConfigsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap()).unwrap();
+        assert_eq!(res.nois_fee, Uint128::from(400u128));
+    }
+
+    #[test]
+    fn cancel_config_change_success() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::ConfigAdmin, OPERATOR);
+        set_config_change_delay(&mut deps, 100);
+
+        let request_set_nois_configs = ExecuteMsg::SetNoisConfigs{
+            nois_proxy: String::from(USER),
+            nois_fee: Uint128::from(300u128),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_nois_configs).unwrap();
+
+        // only nois admin can cancel nois config change
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), ExecuteMsg::CancelConfigChange{change_id: 2}).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::CancelConfigChange{change_id: 2}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","cancel_config_change")
+                                    .add_attribute("change_id", "2")
+                                    .add_attribute("kind", "nois_configs")
+                                    .add_attribute("sender", CREATOR));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 2}).unwrap_err();
+        match res {
+            ContractError::ConfigChangeNotFound{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn set_config_change_delay_fail_with_unauthorized() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::ConfigAdmin, OPERATOR);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), ExecuteMsg::SetConfigChangeDelay{delay: 0}).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
     /***** Set Time Configs *****/
     #[test]
     fn set_time_configs_fail_with_unauthorized() {
@@ -174,6 +495,66 @@ mod unit_tests {
                                     .add_attribute("owner",  CREATOR));
     }
 
+    /***** Set Gas Configs *****/
+    fn set_gas_price(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, callback_gas_price: Decimal) {
+        let request_set_gas_configs = ExecuteMsg::SetGasConfigs{
+            max_callback_gas: MAX_CALLBACK_GAS,
+            callback_gas_price,
+        };
+        // config change delay is zero in default setup, so gas configs are applied right away
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_gas_configs).unwrap();
+    }
+
+    #[test]
+    fn set_gas_configs_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        let request_set_gas_configs = ExecuteMsg::SetGasConfigs{
+            max_callback_gas: MAX_CALLBACK_GAS,
+            callback_gas_price: Decimal::permille(1),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_set_gas_configs).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn set_gas_configs_fail_with_callback_gas_limit_exceeded() {
+        let mut deps = default_setup();
+
+        let request_set_gas_configs = ExecuteMsg::SetGasConfigs{
+            max_callback_gas: CALLBACK_LIMIT_GAS - 1,
+            callback_gas_price: Decimal::permille(1),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_gas_configs).unwrap_err();
+        match res {
+            ContractError::CallbackGasLimitExceeded{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn set_gas_configs_success() {
+        let mut deps = default_setup();
+
+        let request_set_gas_configs = ExecuteMsg::SetGasConfigs{
+            max_callback_gas: MAX_CALLBACK_GAS,
+            callback_gas_price: Decimal::permille(1),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_gas_configs).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","set_gas_config")
+                                    .add_attribute("max_callback_gas", MAX_CALLBACK_GAS.to_string())
+                                    .add_attribute("callback_gas_price", "0.001")
+                                    .add_attribute("change_id", "1")
+                                    .add_attribute("effective_time", mock_env().block.time.seconds().to_string())
+                                    .add_attribute("owner",  CREATOR));
+    }
+
     /***** Set Nois Configs *****/
     #[test]
     fn set_nois_configs_fail_with_unauthorized() {
@@ -225,85 +606,531 @@ mod unit_tests {
         assert_eq!(res, Response::new().add_attribute("action","set_nois_config")
                                     .add_attribute("nois_proxy", nois_proxy)
                                     .add_attribute("nois_fee", nois_fee)
+                                    .add_attribute("change_id", "1")
+                                    .add_attribute("effective_time", mock_env().block.time.seconds().to_string())
                                     .add_attribute("owner",  CREATOR));
     }
 
-    /***** Register Bot *****/
-    fn register_bot(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
-        let hashed_api_key = String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==");
-        let moniker = String::from("test bot");
-
-        let request_register_bot = ExecuteMsg::RegisterBot {
-            hashed_api_key: hashed_api_key.clone(),
-            moniker: moniker.clone(),
+    /***** Ownership *****/
+    fn propose_owner(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, expiry: Option<u64>) {
+        let request_propose_owner = ExecuteMsg::ProposeOwner{
+            new_owner: String::from(NEW_OWNER),
+            expiry,
         };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_register_bot).unwrap();
-        assert_eq!(res, Response::new().add_attribute("action","register_bot")
-                                .add_attribute("hashed_api_key", hashed_api_key)
-                                .add_attribute("moniker", moniker)
-                                .add_attribute("bot_address", BOT));
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_propose_owner).unwrap();
+    }
+
+    fn set_time_configs_msg() -> ExecuteMsg {
+        ExecuteMsg::SetTimeConfigs{
+            time_expired: 10,
+            time_per_block: 5
+        }
     }
 
     #[test]
-    fn register_bot_success() {
+    fn propose_owner_fail_with_unauthorized() {
         let mut deps = default_setup();
 
-        register_bot(&mut deps);
-    }
+        let request_propose_owner = ExecuteMsg::ProposeOwner{
+            new_owner: String::from(USER),
+            expiry: None,
+        };
 
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_propose_owner).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
 
     #[test]
-    fn register_bot_fail_with_duplicate_address() {
+    fn propose_owner_success() {
         let mut deps = default_setup();
 
-        let hashed_api_key = String::from("test hashed api key");
-        let moniker = String::from("test bot");
+        let request_propose_owner = ExecuteMsg::ProposeOwner{
+            new_owner: String::from(NEW_OWNER),
+            expiry: Some(100),
+        };
 
-        let request_register_bot = ExecuteMsg::RegisterBot {
-            hashed_api_key: hashed_api_key.clone(),
-            moniker: moniker.clone(),
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_propose_owner).unwrap();
+        let expires_at = mock_env().block.time.plus_seconds(100);
+        assert_eq!(res, Response::new().add_attribute("action","propose_owner")
+                                    .add_attribute("pending_owner", NEW_OWNER)
+                                    .add_attribute("owner", CREATOR)
+                                    .add_attribute("expires_at", expires_at.seconds().to_string()));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwnership{}).unwrap();
+        assert_eq!(res, to_binary(&OwnershipQuery{
+            owner: Some(String::from(CREATOR)),
+            pending_owner: Some(String::from(NEW_OWNER)),
+            pending_expires_at: Some(expires_at),
+        }).unwrap());
+
+        // proposed owner has no right until it accepts
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NEW_OWNER, &[]), set_time_configs_msg()).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
         };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_register_bot.clone()).unwrap();
-        assert_eq!(res, Response::new().add_attribute("action","register_bot")
-                            .add_attribute("hashed_api_key", hashed_api_key)
-                            .add_attribute("moniker", moniker)
-                            .add_attribute("bot_address", BOT));
-        
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_register_bot).unwrap_err();
+    }
+
+    #[test]
+    fn accept_ownership_success() {
+        let mut deps = default_setup();
+        propose_owner(&mut deps, Some(100));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NEW_OWNER, &[]), ExecuteMsg::AcceptOwnership{}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","accept_ownership")
+                                    .add_attribute("previous_owner", CREATOR)
+                                    .add_attribute("owner", NEW_OWNER));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwnership{}).unwrap();
+        assert_eq!(res, to_binary(&OwnershipQuery{
+            owner: Some(String::from(NEW_OWNER)),
+            pending_owner: None,
+            pending_expires_at: None,
+        }).unwrap());
+
+        // only new owner can manage contract
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), set_time_configs_msg()).unwrap_err();
         match res {
-            ContractError::AddressAlreadyRegistered{} => {},
+            ContractError::Unauthorized{} => {},
             _ => panic!(),
         };
+        execute(deps.as_mut(), mock_env(), mock_info(NEW_OWNER, &[]), set_time_configs_msg()).unwrap();
     }
 
-    /***** Update Bot *****/
     #[test]
-    fn update_bot_success() {
+    fn accept_ownership_fail_with_unauthorized() {
         let mut deps = default_setup();
+        propose_owner(&mut deps, None);
 
-        let env: Env = Env {
-            block: BlockInfo {
-                height: 0,
-                time: Timestamp::from_seconds(0),
-                chain_id: "euphoria-2".to_string()
-            },
-            contract: ContractInfo {
-                address: Addr::unchecked(CONTRACT_ADDR)
-            },
-            transaction: None,
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), ExecuteMsg::AcceptOwnership{}).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
         };
-        let hashed_api_key = String::from("test hashed api key");
-        let moniker = String::from("test bot");
+    }
 
-        let request_register_bot = ExecuteMsg::RegisterBot {
-            hashed_api_key: hashed_api_key.clone(),
-            moniker: moniker.clone(),
+    #[test]
+    fn accept_ownership_fail_with_expired_proposal() {
+        let mut deps = default_setup();
+        propose_owner(&mut deps, Some(100));
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+
+        let res = execute(deps.as_mut(), env, mock_info(NEW_OWNER, &[]), ExecuteMsg::AcceptOwnership{}).unwrap_err();
+        match res {
+            ContractError::OwnershipProposalExpired{} => {},
+            _ => panic!(),
         };
-        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_register_bot).unwrap();
-        assert_eq!(res, Response::new().add_attribute("action","register_bot")
-                                .add_attribute("hashed_api_key", hashed_api_key)
-                                .add_attribute("moniker", moniker)
-                                .add_attribute("bot_address", BOT));
+    }
+
+    #[test]
+    fn cancel_ownership_proposal_success() {
+        let mut deps = default_setup();
+        propose_owner(&mut deps, None);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::CancelOwnershipProposal{}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","cancel_ownership_proposal")
+                                    .add_attribute("owner", CREATOR));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NEW_OWNER, &[]), ExecuteMsg::AcceptOwnership{}).unwrap_err();
+        match res {
+            ContractError::NoPendingOwner{} => {},
+            _ => panic!(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::CancelOwnershipProposal{}).unwrap_err();
+        match res {
+            ContractError::NoPendingOwner{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn renounce_ownership_success() {
+        let mut deps = default_setup();
+        propose_owner(&mut deps, None);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::RenounceOwnership{}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","renounce_ownership")
+                                    .add_attribute("previous_owner", CREATOR));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwnership{}).unwrap();
+        assert_eq!(res, to_binary(&OwnershipQuery{
+            owner: None,
+            pending_owner: None,
+            pending_expires_at: None,
+        }).unwrap());
+
+        // pending proposal is dropped and owner only messages are disabled
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NEW_OWNER, &[]), ExecuteMsg::AcceptOwnership{}).unwrap_err();
+        match res {
+            ContractError::NoPendingOwner{} => {},
+            _ => panic!(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), set_time_configs_msg()).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    /***** Roles *****/
+    fn grant_role(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, role: Role, address: &str) {
+        let request_grant_role = ExecuteMsg::GrantRole{
+            role,
+            address: String::from(address),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_grant_role).unwrap();
+    }
+
+    #[test]
+    fn grant_role_fail_with_unauthorized() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::BotManager, OPERATOR);
+
+        // members can not grant roles, even their own one
+        let request_grant_role = ExecuteMsg::GrantRole{
+            role: Role::BotManager,
+            address: String::from(USER),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_grant_role).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn grant_role_success() {
+        let mut deps = default_setup();
+
+        let request_grant_role = ExecuteMsg::GrantRole{
+            role: Role::ConfigAdmin,
+            address: String::from(OPERATOR),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_grant_role).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","grant_role")
+                                    .add_attribute("role", "config_admin")
+                                    .add_attribute("address", OPERATOR)
+                                    .add_attribute("owner", CREATOR));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::HasRole{role: Role::ConfigAdmin, address: String::from(OPERATOR)}).unwrap();
+        assert_eq!(res, to_binary(&true).unwrap());
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::HasRole{role: Role::NoisAdmin, address: String::from(OPERATOR)}).unwrap();
+        assert_eq!(res, to_binary(&false).unwrap());
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::HasRole{role: Role::NoisAdmin, address: String::from(CREATOR)}).unwrap();
+        assert_eq!(res, to_binary(&true).unwrap());
+
+        // config admin can change configs but can not redirect nois proxy
+        let request_set_time_configs = ExecuteMsg::SetTimeConfigs{
+            time_expired: 10,
+            time_per_block: 5
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_set_time_configs).unwrap();
+
+        let request_set_nois_configs = ExecuteMsg::SetNoisConfigs{
+            nois_proxy: String::from(USER),
+            nois_fee: Uint128::from(300u128),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_set_nois_configs).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn revoke_role_success() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::BotManager, OPERATOR);
+
+        let request_revoke_role = ExecuteMsg::RevokeRole{
+            role: Role::BotManager,
+            address: String::from(OPERATOR),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_revoke_role.clone()).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","revoke_role")
+                                    .add_attribute("role", "bot_manager")
+                                    .add_attribute("address", OPERATOR)
+                                    .add_attribute("owner", CREATOR));
+
+        let request_remove_bot = ExecuteMsg::RemoveBot{
+            address: String::from(BOT),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_remove_bot).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_revoke_role).unwrap_err();
+        match res {
+            ContractError::RoleNotGranted{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn list_role_members_success() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::Pauser, "pauser a");
+        grant_role(&mut deps, Role::Pauser, "pauser b");
+        grant_role(&mut deps, Role::Pauser, "pauser c");
+        grant_role(&mut deps, Role::Treasurer, "treasurer");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListRoleMembers{role: Role::Pauser, start_after: None, limit: Some(2)}).unwrap();
+        assert_eq!(res, to_binary(&RoleMembersQuery{
+            members: vec![String::from("pauser a"), String::from("pauser b")],
+        }).unwrap());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListRoleMembers{role: Role::Pauser, start_after: Some(String::from("pauser b")), limit: None}).unwrap();
+        assert_eq!(res, to_binary(&RoleMembersQuery{
+            members: vec![String::from("pauser c")],
+        }).unwrap());
+    }
+
+    /***** Pause *****/
+    fn set_pause_status(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, 
+        requests: Option<bool>,
+        random_org: Option<bool>,
+        nois: Option<bool>,
+        bot_registration: Option<bool>,
+    ) {
+        let request_set_pause_status = ExecuteMsg::SetPauseStatus{
+            requests,
+            random_org,
+            nois,
+            bot_registration,
+            expiry_extension: Some(100),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_pause_status).unwrap();
+    }
+
+    #[test]
+    fn set_pause_status_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        let request_set_pause_status = ExecuteMsg::SetPauseStatus{
+            requests: Some(true),
+            random_org: None,
+            nois: None,
+            bot_registration: None,
+            expiry_extension: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_set_pause_status).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn set_pause_status_success() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::Pauser, OPERATOR);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetPauseStatus{}).unwrap();
+        assert_eq!(res, to_binary(&PauseStatus::default()).unwrap());
+
+        let request_set_pause_status = ExecuteMsg::SetPauseStatus{
+            requests: None,
+            random_org: Some(true),
+            nois: None,
+            bot_registration: Some(true),
+            expiry_extension: Some(100),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_set_pause_status).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","set_pause_status")
+                                    .add_attribute("requests", "false")
+                                    .add_attribute("random_org", "true")
+                                    .add_attribute("nois", "false")
+                                    .add_attribute("bot_registration", "true")
+                                    .add_attribute("expiry_extension", "100")
+                                    .add_attribute("pauser", OPERATOR));
+
+        // unset flags keep their value
+        let request_set_pause_status = ExecuteMsg::SetPauseStatus{
+            requests: None,
+            random_org: Some(false),
+            nois: None,
+            bot_registration: None,
+            expiry_extension: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_set_pause_status).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetPauseStatus{}).unwrap();
+        assert_eq!(res, to_binary(&PauseStatus{
+            requests: false,
+            random_org: false,
+            nois: false,
+            bot_registration: true,
+            expiry_extension: 100,
+        }).unwrap());
+    }
+
+    #[test]
+    fn paused_operations_fail_with_paused() {
+        let mut deps = default_setup();
+        register_bot(&mut deps);
+        set_pause_status(&mut deps, Some(true), Some(true), Some(true), Some(true));
+
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: make_commit_id(USER.to_string(), 0u64),
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from("test hashed api key"),
+            moniker: String::from("test bot"),
+        };
+
+        let cases = vec![
+            (mock_info(USER, &coins(600u128, DENOM)), request_hex_randomness, "requests"),
+            (mock_info(BOT, &[]), request_add_randomness, "random_org"),
+            (mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive, "nois"),
+            (mock_info(USER, &[]), request_register_bot, "bot_registration"),
+        ];
+
+        for (info, msg, operation) in cases {
+            let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            match res {
+                ContractError::Paused{operation: v} => {assert_eq!(v, operation)},
+                _ => panic!(),
+            };
+        }
+    }
+
+    #[test]
+    fn request_randomness_extends_expiry_while_source_paused() {
+        let mut deps = default_setup();
+        set_pause_status(&mut deps, None, Some(true), None, None);
+
+        request_hex_randomness(&mut deps);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitment{id: make_commit_id(USER.to_string(), 0u64)}).unwrap();
+        let commitment: Option<CommitmentQuery> = from_binary(&res).unwrap();
+        assert_eq!(commitment.unwrap().commitment.expired_time, Timestamp::from_seconds(1675739161 + 100));
+    }
+
+    /***** Register Bot *****/
+    fn register_bot(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let hashed_api_key = String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==");
+        let moniker = String::from("test bot");
+
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: hashed_api_key.clone(),
+            moniker: moniker.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","register_bot")
+                                .add_attribute("hashed_api_key", hashed_api_key)
+                                .add_attribute("moniker", moniker)
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE))
+                                .add_attribute("status", "active")
+                                .add_attribute("bot_address", BOT));
+    }
+
+    #[test]
+    fn register_bot_success() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+    }
+
+
+    #[test]
+    fn register_bot_fail_with_duplicate_address() {
+        let mut deps = default_setup();
+
+        let hashed_api_key = String::from("test hashed api key");
+        let moniker = String::from("test bot");
+
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: hashed_api_key.clone(),
+            moniker: moniker.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot.clone()).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","register_bot")
+                            .add_attribute("hashed_api_key", hashed_api_key)
+                            .add_attribute("moniker", moniker)
+                            .add_attribute("bonded", Uint128::from(MIN_STAKE))
+                            .add_attribute("status", "active")
+                            .add_attribute("bot_address", BOT));
+        
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap_err();
+        match res {
+            ContractError::AddressAlreadyRegistered{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn register_bot_fail_with_api_key_in_use() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        // another address can not register api key of a registered bot
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q=="),
+            moniker: String::from("copy bot"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap_err();
+        match res {
+            ContractError::ApiKeyInUse{} => {},
+            _ => panic!(),
+        };
+    }
+
+    /***** Update Bot *****/
+    #[test]
+    fn update_bot_success() {
+        let mut deps = default_setup();
+
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(0),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let hashed_api_key = String::from("test hashed api key");
+        let moniker = String::from("test bot");
+
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: hashed_api_key.clone(),
+            moniker: moniker.clone(),
+        };
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","register_bot")
+                                .add_attribute("hashed_api_key", hashed_api_key)
+                                .add_attribute("moniker", moniker)
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE))
+                                .add_attribute("status", "active")
+                                .add_attribute("bot_address", BOT));
 
 
         let env: Env = Env {
@@ -369,10 +1196,12 @@ mod unit_tests {
             hashed_api_key: hashed_api_key.clone(),
             moniker: moniker.clone(),
         };
-        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_register_bot).unwrap();
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
         assert_eq!(res, Response::new().add_attribute("action","register_bot")
                                 .add_attribute("hashed_api_key", hashed_api_key)
                                 .add_attribute("moniker", moniker)
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE))
+                                .add_attribute("status", "active")
                                 .add_attribute("bot_address", BOT));
 
 
@@ -402,66 +1231,2597 @@ mod unit_tests {
         };
     }
 
-    /***** Remove Bot *****/
-    #[test]
-    fn remove_bot_success() {
-        let mut deps = default_setup();
+    /***** Api Keys *****/
+    const NEW_HASHED_API_KEY: &str = "new hashed api key";
 
-        register_bot(&mut deps);
+    fn env_after(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
 
-        let request_remove_bot = ExecuteMsg::RemoveBot{
-            address: BOT.to_string()
+    fn add_randomness_at(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: Env) -> Result<Response, ContractError> {
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
         };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_remove_bot).unwrap();
-        assert_eq!(res, Response::new().add_attribute("action","remove_bot")
-                            .add_attribute("bot_addr", String::from(BOT))
-                            .add_attribute("owner", String::from(CREATOR)));
+        execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness)
     }
 
     #[test]
-    fn remove_bot_fail_with_unauthorized() {
+    fn set_api_key_policy_success() {
         let mut deps = default_setup();
 
-        register_bot(&mut deps);
+        let request_set_api_key_policy = ExecuteMsg::SetApiKeyPolicy {
+            update_cooldown: 20,
+            grace_period: 30,
+            max_api_keys: 5,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_api_key_policy).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","set_api_key_policy")
+                                .add_attribute("update_cooldown", "20")
+                                .add_attribute("grace_period", "30")
+                                .add_attribute("max_api_keys", "5")
+                                .add_attribute("change_id", "1")
+                                .add_attribute("effective_time", mock_env().block.time.seconds().to_string())
+                                .add_attribute("owner", CREATOR));
+
+        let res: ConfigsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap()).unwrap();
+        assert_eq!(res.api_key_update_cooldown, 20);
+        assert_eq!(res.api_key_grace_period, 30);
+        assert_eq!(res.max_api_keys, 5);
+    }
 
-        let request_remove_bot = ExecuteMsg::RemoveBot{
-            address: BOT.to_string()
+    #[test]
+    fn set_api_key_policy_fail_with_zero_max_api_keys() {
+        let mut deps = default_setup();
+
+        let request_set_api_key_policy = ExecuteMsg::SetApiKeyPolicy {
+            update_cooldown: 20,
+            grace_period: 30,
+            max_api_keys: 0,
         };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_remove_bot).unwrap_err();
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_api_key_policy).unwrap_err();
         match res {
-            ContractError::Unauthorized{} => {},
+            ContractError::InvalidApiKeyPolicy{} => {},
             _ => panic!(),
         };
     }
 
     #[test]
-    fn remove_bot_fail_with_invalid_bot_address() {
+    fn add_api_key_success() {
         let mut deps = default_setup();
 
         register_bot(&mut deps);
 
-        let request_remove_bot = ExecuteMsg::RemoveBot{
-            address: "".to_string()
-        };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_remove_bot);
-        match res {
-            Err(_) =>{},
-            Ok(_) => panic!(),
+        let request_add_api_key = ExecuteMsg::AddApiKey {
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
         };
+        let res = execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN), mock_info(BOT, &[]), request_add_api_key).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","add_api_key")
+                                .add_attribute("hashed_api_key", NEW_HASHED_API_KEY)
+                                .add_attribute("bot_address", BOT));
+
+        let bot_info = query_bot_info(&deps);
+        assert_eq!(bot_info.api_keys.len(), 2);
+        assert_eq!(bot_info.api_keys[1], ApiKey{
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
+            added_time: env_after(API_KEY_UPDATE_COOLDOWN).block.time,
+            valid_until: None,
+        });
+
+        // both keys are in use
+        add_randomness_at(&mut deps, env_after(API_KEY_UPDATE_COOLDOWN)).unwrap();
     }
 
-    /***** Request Randomness *****/
-    fn request_hex_randomness(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
-        let request_id: String = String::from("test id 1");
+    #[test]
+    fn add_api_key_fail_with_api_key_in_use() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
+            moniker: String::from("other bot"),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+
+        // bot can not add api key of another registered bot
+        let request_add_api_key = ExecuteMsg::AddApiKey {
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
+        };
+        let res = execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN), mock_info(BOT, &[]), request_add_api_key).unwrap_err();
+        match res {
+            ContractError::ApiKeyInUse{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_api_key_fail_with_too_many_api_keys() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        for i in 1..MAX_API_KEYS {
+            let request_add_api_key = ExecuteMsg::AddApiKey {
+                hashed_api_key: format!("hashed api key {}", i),
+            };
+            execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN * i as u64), mock_info(BOT, &[]), request_add_api_key).unwrap();
+        }
+
+        let request_add_api_key = ExecuteMsg::AddApiKey {
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
+        };
+        let res = execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN * MAX_API_KEYS as u64), mock_info(BOT, &[]), request_add_api_key).unwrap_err();
+        match res {
+            ContractError::TooManyApiKeys{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_api_key_fail_with_to_many_action() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_add_api_key = ExecuteMsg::AddApiKey {
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
+        };
+        let res = execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN - 1), mock_info(BOT, &[]), request_add_api_key).unwrap_err();
+        match res {
+            ContractError::ToManyAction{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn retire_api_key_fail_with_last_api_key() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_retire_api_key = ExecuteMsg::RetireApiKey {
+            hashed_api_key: String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q=="),
+        };
+        let res = execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN), mock_info(BOT, &[]), request_retire_api_key).unwrap_err();
+        match res {
+            ContractError::LastApiKey{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_fail_with_retired_api_key_after_grace_period() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_add_api_key = ExecuteMsg::AddApiKey {
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
+        };
+        execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN), mock_info(BOT, &[]), request_add_api_key).unwrap();
+
+        let request_retire_api_key = ExecuteMsg::RetireApiKey {
+            hashed_api_key: String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q=="),
+        };
+        let retire_time = API_KEY_UPDATE_COOLDOWN * 2;
+        let res = execute(deps.as_mut(), env_after(retire_time), mock_info(BOT, &[]), request_retire_api_key).unwrap();
+        assert_eq!(res.attributes[2].value, env_after(retire_time + API_KEY_GRACE_PERIOD).block.time.seconds().to_string());
+
+        let res = add_randomness_at(&mut deps, env_after(retire_time + API_KEY_GRACE_PERIOD)).unwrap_err();
+        match res {
+            ContractError::InvalidApiKey{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn update_bot_success_with_retired_api_key_in_grace_period() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        // rotate to a new key, payloads signed under the old key are still accepted for a while
+        let request_update_bot = ExecuteMsg::UpdateBot {
+            hashed_api_key: String::from(NEW_HASHED_API_KEY),
+            moniker: String::from("test bot"),
+        };
+        execute(deps.as_mut(), env_after(API_KEY_UPDATE_COOLDOWN), mock_info(BOT, &[]), request_update_bot).unwrap();
+
+        let bot_info = query_bot_info(&deps);
+        assert_eq!(bot_info.api_keys[0].valid_until, Some(env_after(API_KEY_UPDATE_COOLDOWN + API_KEY_GRACE_PERIOD).block.time));
+        assert_eq!(bot_info.api_keys[1].valid_until, None);
+
+        add_randomness_at(&mut deps, env_after(API_KEY_UPDATE_COOLDOWN + API_KEY_GRACE_PERIOD - 1)).unwrap();
+    }
+
+    /***** Remove Bot *****/
+    #[test]
+    fn remove_bot_success() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_remove_bot = ExecuteMsg::RemoveBot{
+            address: BOT.to_string()
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_remove_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","remove_bot")
+                            .add_attribute("bot_addr", String::from(BOT))
+                            .add_attribute("unbonding", Uint128::from(MIN_STAKE))
+                            .add_attribute("owner", String::from(CREATOR)));
+    }
+
+    #[test]
+    fn remove_bot_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_remove_bot = ExecuteMsg::RemoveBot{
+            address: BOT.to_string()
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_remove_bot).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn remove_bot_fail_with_invalid_bot_address() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_remove_bot = ExecuteMsg::RemoveBot{
+            address: "".to_string()
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_remove_bot);
+        match res {
+            Err(_) =>{},
+            Ok(_) => panic!(),
+        };
+    }
+
+    /***** Deregister Bot *****/
+    #[test]
+    fn deregister_bot_success() {
+        let mut deps = default_setup();
+
+        fulfill_hex_randomness_by_bot(&mut deps);
+
+        let request_deregister_bot = ExecuteMsg::DeregisterBot {
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_deregister_bot).unwrap();
+
+        let reregister_time = mock_env().block.time.plus_seconds(UNBONDING_PERIOD);
+        assert_eq!(res, Response::new().add_message(BankMsg::Send {
+                                    to_address: String::from(BOT),
+                                    amount: coins(FEE - PROTOCOL_FEE, DENOM),
+                                })
+                                .add_attribute("action", "deregister_bot")
+                                .add_attribute("rewards", Uint128::from(FEE - PROTOCOL_FEE))
+                                .add_attribute("recipient", BOT)
+                                .add_attribute("unbonding", Uint128::from(MIN_STAKE))
+                                .add_attribute("reregister_time", reregister_time.seconds().to_string())
+                                .add_attribute("bot_address", BOT));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(BOT) }).unwrap();
+        assert_eq!(res, to_binary(&None::<BotInfoQuery>).unwrap());
+
+        let tombstone: Option<BotTombstone> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetBotTombstone{ address: String::from(BOT) }).unwrap()).unwrap();
+        assert_eq!(tombstone, Some(BotTombstone{
+            moniker: String::from("test bot"),
+            hashed_api_keys: vec![String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==")],
+            removed_time: mock_env().block.time,
+            reregister_time,
+        }));
+
+        let treasury = query_treasury(&deps);
+        assert_eq!(treasury.bot_rewards, Uint128::zero());
+        assert_eq!(treasury.staked, Uint128::from(MIN_STAKE));
+
+        // stake is still unbonding and slashable
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), ExecuteMsg::WithdrawStake{}).unwrap_err();
+        match res {
+            ContractError::NoUnbondedStake{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn deregister_bot_fail_with_unregister_bot() {
+        let mut deps = default_setup();
+
+        let request_deregister_bot = ExecuteMsg::DeregisterBot {
+            recipient: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_deregister_bot).unwrap_err();
+        match res {
+            ContractError::UnregisteredAddress{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn register_bot_fail_with_deregistered_bot() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), ExecuteMsg::DeregisterBot{recipient: None}).unwrap();
+
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from("test hashed api key"),
+            moniker: String::from("test bot"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot.clone()).unwrap_err();
+        match res {
+            ContractError::BotDeregistered{} => {},
+            _ => panic!(),
+        };
+
+        // bot can come back once its stake would have been released
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(UNBONDING_PERIOD);
+        execute(deps.as_mut(), env, mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+    }
+
+    #[test]
+    fn register_bot_fail_with_deregistered_api_key() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), ExecuteMsg::DeregisterBot{recipient: None}).unwrap();
+
+        // same api key under a new address
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q=="),
+            moniker: String::from("test bot"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(GAME, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap_err();
+        match res {
+            ContractError::ApiKeyDeregistered{} => {},
+            _ => panic!(),
+        };
+    }
+
+    /***** Bot Approval *****/
+    fn register_bot_in_allowlist_mode(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let request_set_registration_mode = ExecuteMsg::SetRegistrationMode {
+            mode: RegistrationMode::Allowlist,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_registration_mode).unwrap();
+
+        register_bot_with_status(deps, "pending_approval");
+    }
+
+    fn register_bot_with_status(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, status: &str) {
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q=="),
+            moniker: String::from("test bot"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+        assert_eq!(res.attributes[4].value, status);
+    }
+
+    #[test]
+    fn set_registration_mode_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        let request_set_registration_mode = ExecuteMsg::SetRegistrationMode {
+            mode: RegistrationMode::Allowlist,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_set_registration_mode).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_fail_with_pending_approval() {
+        let mut deps = default_setup();
+
+        register_bot_in_allowlist_mode(&mut deps);
+
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::BotNotActive{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn approve_bot_success() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::BotManager, OPERATOR);
+
+        register_bot_in_allowlist_mode(&mut deps);
+
+        let request_approve_bot = ExecuteMsg::ApproveBot {
+            address: String::from(BOT),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_approve_bot.clone()).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","approve_bot")
+                                .add_attribute("bot_addr", BOT)
+                                .add_attribute("owner", OPERATOR));
+
+        assert_eq!(query_bot_info(&deps).status, BotStatus::Active);
+
+        // approved bot can add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap();
+
+        // bot can only be approved once
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_approve_bot).unwrap_err();
+        match res {
+            ContractError::BotNotPending{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn approve_bot_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        register_bot_in_allowlist_mode(&mut deps);
+
+        let request_approve_bot = ExecuteMsg::ApproveBot {
+            address: String::from(BOT),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_approve_bot).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn reject_bot_success() {
+        let mut deps = default_setup();
+
+        register_bot_in_allowlist_mode(&mut deps);
+
+        let request_reject_bot = ExecuteMsg::RejectBot {
+            address: String::from(BOT),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_reject_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","reject_bot")
+                                .add_attribute("bot_addr", BOT)
+                                .add_attribute("unbonding", Uint128::from(MIN_STAKE))
+                                .add_attribute("owner", CREATOR));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(BOT) }).unwrap();
+        assert_eq!(res, to_binary(&None::<BotInfoQuery>).unwrap());
+
+        // stake of rejected bot is released right away
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), ExecuteMsg::WithdrawStake{}).unwrap();
+        assert_eq!(res.messages, vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from(BOT),
+            amount: coins(MIN_STAKE, DENOM),
+        })]);
+    }
+
+    #[test]
+    fn reject_bot_fail_with_active_bot() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_reject_bot = ExecuteMsg::RejectBot {
+            address: String::from(BOT),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_reject_bot).unwrap_err();
+        match res {
+            ContractError::BotNotPending{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn list_bots_success_with_status() {
+        let mut deps = default_setup();
+
+        // first bot registers in open mode, second one has to be approved
+        register_bot(&mut deps);
+        let request_set_registration_mode = ExecuteMsg::SetRegistrationMode {
+            mode: RegistrationMode::Allowlist,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_registration_mode).unwrap();
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from("test hashed api key"),
+            moniker: String::from("applicant"),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(GAME, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+
+        let res: BotsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListBots{status: Some(BotStatus::PendingApproval), start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.bots.len(), 1);
+        assert_eq!(res.bots[0].address, GAME);
+        assert_eq!(res.bots[0].status, BotStatus::PendingApproval);
+
+        let res: BotsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListBots{status: None, start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.bots.iter().map(|bot| bot.address.clone()).collect::<Vec<String>>(), vec![String::from(BOT), String::from(GAME)]);
+
+        let res: BotsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListBots{status: None, start_after: Some(String::from(BOT)), limit: Some(1)}).unwrap()).unwrap();
+        assert_eq!(res.bots.len(), 1);
+        assert_eq!(res.bots[0].address, GAME);
+
+        // approved bot moves to active bots
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::ApproveBot{address: String::from(GAME)}).unwrap();
+        let res: BotsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListBots{status: Some(BotStatus::PendingApproval), start_after: None, limit: None}).unwrap()).unwrap();
+        assert!(res.bots.is_empty());
+        let res: BotsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListBots{status: Some(BotStatus::Active), start_after: Some(String::from(BOT)), limit: None}).unwrap()).unwrap();
+        assert_eq!(res.bots.len(), 1);
+        assert_eq!(res.bots[0].address, GAME);
+
+        // removed bot is not listed anymore
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::RemoveBot{address: String::from(BOT)}).unwrap();
+        let res: BotsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListBots{status: Some(BotStatus::Active), start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.bots.iter().map(|bot| bot.address.clone()).collect::<Vec<String>>(), vec![String::from(GAME)]);
+    }
+
+    /***** Staking *****/
+    fn query_bot_info(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> BotInfoQuery {
+        let res: Option<BotInfoQuery> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(BOT) }).unwrap()).unwrap();
+        res.unwrap()
+    }
+
+    #[test]
+    fn set_staking_configs_success() {
+        let mut deps = default_setup();
+
+        let request_set_staking_configs = ExecuteMsg::SetStakingConfigs {
+            min_stake: Uint128::from(2000u128),
+            unbonding_period: 200,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_staking_configs).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","set_staking_config")
+                                .add_attribute("min_stake", Uint128::from(2000u128))
+                                .add_attribute("unbonding_period", "200")
+                                .add_attribute("change_id", "1")
+                                .add_attribute("effective_time", mock_env().block.time.seconds().to_string())
+                                .add_attribute("owner", CREATOR));
+
+        let res: ConfigsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap()).unwrap();
+        assert_eq!(res.min_stake, Uint128::from(2000u128));
+        assert_eq!(res.unbonding_period, 200);
+    }
+
+    #[test]
+    fn register_bot_fail_with_insufficient_stake() {
+        let mut deps = default_setup();
+
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: String::from("test hashed api key"),
+            moniker: String::from("test bot"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE - 1, DENOM)), request_register_bot.clone()).unwrap_err();
+        match res {
+            ContractError::InsufficientStake{} => {},
+            _ => panic!(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE, "uatom")), request_register_bot).unwrap_err();
+        match res {
+            ContractError::UnexpectedDenom{denom: v} => {assert_eq!(v, "uatom")},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn bond_stake_success() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(500u128, DENOM)), ExecuteMsg::BondStake{}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","bond_stake")
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE + 500))
+                                .add_attribute("bot", BOT));
+
+        let res: TreasuryQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap();
+        assert_eq!(res.staked, Uint128::from(MIN_STAKE + 500));
+    }
+
+    #[test]
+    fn unbond_stake_success() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_unbond_stake = ExecuteMsg::UnbondStake {
+            amount: Uint128::from(400u128),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_unbond_stake).unwrap();
+        let release_time = mock_env().block.time.plus_seconds(UNBONDING_PERIOD);
+        assert_eq!(res, Response::new().add_attribute("action","unbond_stake")
+                                .add_attribute("amount", Uint128::from(400u128))
+                                .add_attribute("release_time", release_time.seconds().to_string())
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE - 400))
+                                .add_attribute("bot", BOT));
+
+        let bot_info = query_bot_info(&deps);
+        assert_eq!(bot_info.bonded, Uint128::from(MIN_STAKE - 400));
+        assert_eq!(bot_info.unbonding, vec![Unbonding{
+            amount: Uint128::from(400u128),
+            release_time,
+        }]);
+
+        // bot is below min stake and can not add randomness anymore
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::InsufficientStake{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn withdraw_stake_success_after_unbonding_period() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_unbond_stake = ExecuteMsg::UnbondStake {
+            amount: Uint128::from(MIN_STAKE),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_unbond_stake).unwrap();
+
+        // stake is locked during unbonding period
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), ExecuteMsg::WithdrawStake{}).unwrap_err();
+        match res {
+            ContractError::NoUnbondedStake{} => {},
+            _ => panic!(),
+        };
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(UNBONDING_PERIOD);
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), ExecuteMsg::WithdrawStake{}).unwrap();
+        assert_eq!(res, Response::new().add_message(BankMsg::Send {
+                                    to_address: String::from(BOT),
+                                    amount: coins(MIN_STAKE, DENOM),
+                                })
+                                .add_attribute("action","withdraw_stake")
+                                .add_attribute("amount", Uint128::from(MIN_STAKE))
+                                .add_attribute("bot", BOT));
+
+        assert_eq!(query_bot_info(&deps).unbonding, vec![]);
+        let res: TreasuryQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap();
+        assert_eq!(res.staked, Uint128::zero());
+    }
+
+    #[test]
+    fn remove_bot_success_with_unbonding_stake() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_remove_bot = ExecuteMsg::RemoveBot{
+            address: BOT.to_string()
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_remove_bot).unwrap();
+
+        // removed bot can still withdraw its stake after unbonding period
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(UNBONDING_PERIOD);
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), ExecuteMsg::WithdrawStake{}).unwrap();
+        assert_eq!(res.messages, vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from(BOT),
+            amount: coins(MIN_STAKE, DENOM),
+        })]);
+    }
+
+    #[test]
+    fn slash_bot_success() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_unbond_stake = ExecuteMsg::UnbondStake {
+            amount: Uint128::from(800u128),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_unbond_stake).unwrap();
+
+        // bonded stake is slashed first, then unbonding stake
+        let request_slash_bot = ExecuteMsg::SlashBot {
+            address: String::from(BOT),
+            amount: Uint128::from(500u128),
+            reason: String::from("reused payload"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_slash_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","slash_bot")
+                                .add_attribute("bot_addr", BOT)
+                                .add_attribute("slashed", Uint128::from(500u128))
+                                .add_attribute("reason", "reused payload")
+                                .add_attribute("owner", CREATOR));
+
+        let bot_info = query_bot_info(&deps);
+        assert_eq!(bot_info.bonded, Uint128::zero());
+        assert_eq!(bot_info.unbonding, vec![Unbonding{
+            amount: Uint128::from(500u128),
+            release_time: mock_env().block.time.plus_seconds(UNBONDING_PERIOD),
+        }]);
+        assert_eq!(bot_info.slashes, vec![Slash{
+            amount: Uint128::from(500u128),
+            reason: String::from("reused payload"),
+            time: mock_env().block.time,
+        }]);
+
+        let res: TreasuryQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap();
+        assert_eq!(res.staked, Uint128::from(500u128));
+        assert_eq!(res.protocol_fees, Uint128::from(500u128));
+    }
+
+    #[test]
+    fn slash_bot_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        register_bot(&mut deps);
+
+        let request_slash_bot = ExecuteMsg::SlashBot {
+            address: String::from(BOT),
+            amount: Uint128::from(500u128),
+            reason: String::from("reused payload"),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_slash_bot).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    /***** Request Randomness *****/
+    fn request_hex_randomness(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let request_id: String = String::from("test id 1");
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: request_id.clone(),
+            num: 1,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(1675739151),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let coin: Coin = Coin{
+            denom: "ueaura".to_string(),
+            amount: Uint128::from(600u128),
+        };
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[coin]), request_hex_randomness).unwrap();
+        
+        let nonce: u64 = 0u64;
+        let commit_id = make_commit_id(USER.to_string(), nonce);
+
+        let sub_msg: SubMsg = SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: NOIS_PROXY_ADDR.to_string(),
+                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
+                                job_id: commit_id.clone() }).unwrap(),
+                funds: coins(NOIS_FEE, DENOM),
+            }
+            .into(),
+            id: NOIS_CALLBACK_REPLY_ID,
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        };
+
+        assert_eq!(res, Response::new().add_submessage(sub_msg)
+                            .add_attribute("action", "request_randomness")
+                            .add_attribute("commitment_id",commit_id)
+                            .add_attribute("request_id",request_id)
+                            .add_attribute("fee", Uint128::from(FEE))
+                            .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string())
+                            .add_attribute("user", String::from(USER)));
+    }
+
+    fn request_int_randomness(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let request_id: String = String::from("test id 1");
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: request_id.clone(),
+            num: 1,
+            min: 0,
+            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(1675739151),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let coin: Coin = Coin{
+            denom: "ueaura".to_string(),
+            amount: Uint128::from(600u128),
+        };
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[coin]), request_int_randomness).unwrap();
+
+        let nonce: u64 = 0u64;
+        let commit_id = make_commit_id(USER.to_string(), nonce);
+
+        let sub_msg: SubMsg = SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: NOIS_PROXY_ADDR.to_string(),
+                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
+                                job_id: commit_id.clone() }).unwrap(),
+                funds: coins(NOIS_FEE, DENOM),
+            }
+            .into(),
+            id: NOIS_CALLBACK_REPLY_ID,
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        };
+
+        assert_eq!(res, Response::new().add_submessage(sub_msg)
+                                .add_attribute("action", "request_randomness")
+                                .add_attribute("commitment_id",commit_id)
+                                .add_attribute("request_id",request_id)
+                                .add_attribute("fee", Uint128::from(FEE))
+                                .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string())
+                                .add_attribute("user", String::from(USER)));
+    }
+
+    #[test]
+    fn request_hex_randomness_success() {
+        let mut deps = default_setup();
+        
+        request_hex_randomness(&mut deps);
+    }
+
+    #[test]
+    fn request_int_randomness_success(){
+        let mut deps = default_setup();
+
+        request_int_randomness(&mut deps);
+    }
+
+
+    #[test]
+    fn request_int_randomness_success_multi_time() {
+        let mut deps = default_setup();
+
+        let request_id: String = String::from("test id 1");
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: request_id.clone(),
+            num: 1,
+            min: 0,
+            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+
+        let coin: Coin = Coin{
+            denom: "ueaura".to_string(),
+            amount: Uint128::from(600u128),
+        };
+
+        // first time
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap();
+
+        let nonce: u64 = 0u64;
+        let commit_id = make_commit_id(USER.to_string(), nonce);
+
+        let sub_msg: SubMsg = SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: NOIS_PROXY_ADDR.to_string(),
+                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
+                                job_id: commit_id.clone() }).unwrap(),
+                funds: coins(NOIS_FEE, DENOM),
+            }
+            .into(),
+            id: NOIS_CALLBACK_REPLY_ID,
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        };
+
+        assert_eq!(res, Response::new().add_submessage(sub_msg)
+                            .add_attribute("action", "request_randomness")
+                            .add_attribute("commitment_id",commit_id)
+                            .add_attribute("request_id",request_id)
+                            .add_attribute("fee", Uint128::from(FEE))
+                            .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string())
+                            .add_attribute("user", String::from(USER)));
+
+        
+        //second time
+        let request_id: String = String::from("test id 2");
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: request_id.clone(),
+            num: 1,
+            min: 0,
+            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+
+        let coin: Coin = Coin{
+            denom: "ueaura".to_string(),
+            amount: Uint128::from(600u128),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap();
+
+        let nonce: u64 = 1u64;
+        let commit_id = make_commit_id(USER.to_string(), nonce);
+
+        let sub_msg: SubMsg = SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: NOIS_PROXY_ADDR.to_string(),
+                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
+                                job_id: commit_id.clone() }).unwrap(),
+                funds: coins(NOIS_FEE, DENOM),
+            }
+            .into(),
+            id: NOIS_CALLBACK_REPLY_ID,
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        };
+
+        assert_eq!(res, Response::new().add_submessage(sub_msg)
+                        .add_attribute("action", "request_randomness")
+                        .add_attribute("commitment_id",commit_id)
+                        .add_attribute("request_id",request_id)
+                        .add_attribute("fee", Uint128::from(FEE))
+                        .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string())
+                        .add_attribute("user", String::from(USER)));
+
+    }
+
+    #[test]
+    fn request_int_randomness_fail_with_invalid_num() {
+        let mut deps = default_setup();
+        
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: "test id 1".to_string(),
+            num: 0,
+            min: 0,
+            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_int_randomness).unwrap_err();
+
+        match res {
+            ContractError::CustomError{val: v} => {assert_eq!(v, "number of randomness must be in range 1..256".to_string())},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn request_int_randomness_fail_with_invalid_denom() {
+        let mut deps = default_setup();
+
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: "test id 1".to_string(),
+            num: 1,
+            min: 0,
+            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+
+        let coin: Coin = Coin{
+            denom: "eaura".to_string(),
+            amount: Uint128::from(1u128),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap_err();
+
+        match res {
+            ContractError::CustomError{val: v} => {assert_eq!(v, "Expected denom ueaura".to_string())},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn request_int_randomness_fail_with_unexpected_denom() {
+        let mut deps = default_setup();
+
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: "test id 1".to_string(),
+            num: 1,
+            min: 0,
+            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+
+        let funds = vec![
+            Coin{denom: String::from(DENOM), amount: Uint128::from(FEE + NOIS_FEE)},
+            Coin{denom: String::from("uatom"), amount: Uint128::from(1u128)},
+        ];
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &funds), request_int_randomness).unwrap_err();
+
+        match res {
+            ContractError::UnexpectedDenom{denom} => {assert_eq!(denom, "uatom".to_string())},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn request_hex_randomness_success_with_surplus_refund() {
+        let mut deps = default_setup();
+
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(1000u128, DENOM)), request_hex_randomness).unwrap();
+
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.messages[1], SubMsg::new(BankMsg::Send {
+            to_address: String::from(USER),
+            amount: coins(1000u128 - FEE - NOIS_FEE, DENOM),
+        }));
+        assert_eq!(res.attributes.last().unwrap().value, (1000u128 - FEE - NOIS_FEE).to_string());
+    }
+
+    #[test]
+    fn request_hex_randomness_fail_with_callback_gas_limit_exceeded() {
+        let mut deps = default_setup();
+
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: Some(MAX_CALLBACK_GAS + 1),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(600u128, DENOM)), request_hex_randomness).unwrap_err();
+
+        match res {
+            ContractError::CallbackGasLimitExceeded{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn request_hex_randomness_with_callback_gas_limit_charges_gas() {
+        let mut deps = default_setup();
+
+        set_gas_price(&mut deps, Decimal::permille(1));
+
+        // 500000 gas * 0.001 = 500 on top of aurand fee and nois fee
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: Some(500000),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(FEE + NOIS_FEE, DENOM)), request_hex_randomness.clone()).unwrap_err();
+        match res {
+            ContractError::CustomError{val: v} => {assert_eq!(v, "Insufficient fee! required 1100ueaura".to_string())},
+            _ => panic!(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(1100u128, DENOM)), request_hex_randomness).unwrap();
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+        assert_eq!(res.attributes[3].value, "800");
+        assert_eq!(res.attributes[4].value, "500000");
+
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: commit_id,
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        // callback uses requested gas limit and treasury collects its share of the whole commitment fee
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].gas_limit, Some(500000));
+        let treasury: TreasuryQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap();
+        assert_eq!(treasury.protocol_fees, Uint128::from(160u128));
+    }
+
+    #[test]
+    fn request_int_randomness_fail_with_insufficent_fee() {
+        let mut deps = default_setup();
+
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: "test id 1".to_string(),
+            num: 1,
+            min: 0,
+            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+
+        let coin: Coin = Coin{
+            denom: "ueaura".to_string(),
+            amount: Uint128::from(1u128),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap_err();
+
+        match res {
+            ContractError::CustomError{val: v} => {assert_eq!(v, "Insufficient fee! required 600ueaura".to_string())},
+            _ => panic!(),
+        };
+    }
+
+    /***** Add Randomness *****/
+
+    const RANDOM_VALUE_TEST: &str = r#"{"method":"generateSignedIntegers","hashedApiKey":"elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==","n":32,"min":0,"max":255,"replacement":true,"base":10,"pregeneratedRandomization":null,"data":[127,12,177,76,70,175,6,221,126,220,251,62,125,122,39,146,236,173,173,240,28,197,116,202,130,36,88,171,55,232,75,86],"license":{"type":"developer","text":"Random values licensed strictly for development and testing only","infoUrl":null},"licenseData":null,"userData":null,"ticketData":null,"completionTime":"2023-02-07 03:05:57Z","serialNumber":489}"#;
+    const SIGNATURE_TEST: &str = "kITMbucgIRih+606JH/zfYDIBqOYbB4VEyjCkLJIteIqMMRMZrFRBPmP4Lm+AXNSr4pl2j5fGBXcBJJUdLb4i1p/o4yI7XMg3B3lxhxbZc0fLQ4oWfPniM7El8T6AzxSgBl+OzPU08A+628j7D88IxaGXk5nzrCOmyYhTElfJNwe7erT2SJu9ydA0bC8OypRxJvfBAq4repxhsYFOG32ZhiTQ60BrjB2cTkgTTsLtBYipvp/sTfMZtUAwZ4wrYmSnBqgAFhM9IvpasrYp/4b2wej4AOKwMD34iipg84+29JwwapRBdWizzUm/TdKMvHUMAnwfyWkGs48mMtVjQstWA6A/gWkQILC5DnWJwF0DG1xOUSWO3lc3ETCDt9kNzO6y43ybYZaTma65w3xlLmuMaJAj1tIRAgHcMIHrlC0nmy9FLKVUf/drjsF5BlKbCIG6mWFuQcG4rNCsLu+3l1DjP5QeJZul9DEREHRtbkPsLCAN/Vxe/M6jieKGEJzoE2FEqeeQZCV5n7ihYVOmcJwvO2e4rBpVuu6/giqB2qd+mNqnwyPoTRn60uZPNpyxzLA+L5VRbzNHIsukQHjAB1wZO7KFomHV0xT8WOHDsTO7QKLE8T5UaEeJZVYFLduj1Eg+b05YvqRV4dW6L6/5oVnHpDEYYsJaS+HeRXrcBS3/Lk=";
+
+    #[test]
+    fn add_randomness_success_with_none_commitment() {
+        let mut deps = default_setup();
+
+        //register bot
+        register_bot(&mut deps);
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from(SIGNATURE_TEST),
+        };
+        
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap();
+
+        let messages: Vec<SubMsg> = Vec::new();
+        assert_eq!(res, Response::new().add_attribute("action","add_randomness")
+                                .add_attribute("random_value", String::from(RANDOM_VALUE_TEST))
+                                .add_attribute("signature", String::from(SIGNATURE_TEST))
+                                .add_attribute("bot", String::from(BOT))
+                                .add_attribute("reward", Uint128::zero())
+                                .add_submessages(messages));
+    }
+
+    #[test]
+    fn add_randomness_success_with_request_hex_randomness_commitment() {
+        let mut deps = default_setup();
+
+        //user request randomness
+        request_hex_randomness(&mut deps);
+
+                                
+        //register bot
+        register_bot(&mut deps);
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from(SIGNATURE_TEST),
+        };
+
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(1675739157),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
+
+        let messages: Vec<SubMsg> = vec![
+            SubMsg {
+                msg: WasmMsg::Execute {
+                    contract_addr: USER.to_string(),
+                    msg: to_binary(&CallbackExecuteMsg::ReceiveHexRandomness{ 
+                        request_id: String::from("test id 1"), 
+                        randomness: vec![String::from("308fb245ab064a15992c03d944baa9e0b9cb253f11f39a697beb021bcb863d9f")],
+                        payload: None,
+                    }).unwrap(),
+                    funds: vec![],
+                }
+                .into(),
+                id: COMMITMENT_CALLBACK_REPLY_ID_START,
+                gas_limit: Some(CALLBACK_LIMIT_GAS),
+                reply_on: ReplyOn::Always,
+            },
+        ];
+
+        assert_eq!(res, Response::new().add_attribute("action","add_randomness")
+                                .add_attribute("random_value", String::from(RANDOM_VALUE_TEST))
+                                .add_attribute("signature", String::from(SIGNATURE_TEST))
+                                .add_attribute("bot", String::from(BOT))
+                                .add_attribute("reward", Uint128::from(FEE - PROTOCOL_FEE))
+                                .add_submessages(messages)
+                                .add_event(Event::new("commitment_fulfilled")
+                                    .add_attribute("commitment_id", make_commit_id(USER.to_string(), 0u64))
+                                    .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string())));
+    }
+
+    #[test]
+    fn add_randomness_success_with_request_int_randomness_commitment() {
+        let mut deps = default_setup();
+
+        //user request randomness
+        request_int_randomness(&mut deps);
+
+        //register bot
+        register_bot(&mut deps);
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from(SIGNATURE_TEST),
+        };
+
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(1675739157),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
+
+        let messages: Vec<SubMsg> = vec![
+            SubMsg {
+                msg: WasmMsg::Execute {
+                    contract_addr: USER.to_string(),
+                    msg: to_binary(&CallbackExecuteMsg::ReceiveIntRandomness{ 
+                        request_id: String::from("test id 1"), 
+                        randomness: vec![208i32],
+                        payload: None,
+                    }).unwrap(),
+                    funds: vec![],
+                }
+                .into(),
+                id: COMMITMENT_CALLBACK_REPLY_ID_START,
+                gas_limit: Some(CALLBACK_LIMIT_GAS),
+                reply_on: ReplyOn::Always,
+            },
+        ];
+
+        assert_eq!(res, Response::new().add_attribute("action","add_randomness")
+                                .add_attribute("random_value", String::from(RANDOM_VALUE_TEST))
+                                .add_attribute("signature", String::from(SIGNATURE_TEST))
+                                .add_attribute("bot", String::from(BOT))
+                                .add_attribute("reward", Uint128::from(FEE - PROTOCOL_FEE))
+                                .add_submessages(messages)
+                                .add_event(Event::new("commitment_fulfilled")
+                                    .add_attribute("commitment_id", make_commit_id(USER.to_string(), 0u64))
+                                    .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string())));
+    }
+
+    #[test]
+    fn add_randomness_fail_with_bot_not_register() {
+        let mut deps = default_setup();
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from(SIGNATURE_TEST),
+        };
+        
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::UnregisteredAddress{} => {},
+            _ => panic!(),
+        };
+    }
+
+    /***** Verification Keys *****/
+    // locally generated RSA key and a payload signed with it
+    const LOCAL_MODULUS: &str = "a5f73b8535d3ad48c5aa77cfc4c4e56a9364a867cd1bd8c013833fb2cb5f8db7caf52a72af8e62c59e6096ce5a2fb8d1281522e495f6dce2e4bd266170060192a3bfbb6ee5ddb38c848f4ad995e34155ab625c19ec3bd6d4d68c0ce3f866a540606446365940630fc4a117d603b0052d4da3daa4f8f7ea7fdc760cd6a3ec2189";
+    const LOCAL_RANDOM_VALUE_TEST: &str = r#"{"method":"generateSignedIntegers","hashedApiKey":"elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==","n":32,"min":0,"max":255,"replacement":true,"base":10,"pregeneratedRandomization":null,"data":[3,141,59,26,53,58,97,93,238,46,26,43,38,32,79,50,28,84,197,169,39,93,75,105,20,9,74,94,45,92,30,78],"license":{"type":"developer","text":"Random values licensed strictly for development and testing only","infoUrl":null},"licenseData":null,"userData":null,"ticketData":null,"completionTime":"2023-02-07 03:05:57Z","serialNumber":1}"#;
+    const LOCAL_SIGNATURE_TEST: &str = "Rfnplpt0XQg1ip+69r/Yxl0O1/x7UDnRpr1pYeiJfYXd0kCggq93rVFQ84FYA3GZ3ToiZZTrlKXEUMdPJfeUSe3fff1mgPl0g6NzqSUMUVOHj03UaLnocGrjN4+0ad75H7MXzAouRpjN2AZ81utJd1+6FKiaHvMNo1WELcOvsyY=";
+
+    fn add_local_verification_key(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, valid_from: Timestamp, valid_until: Option<Timestamp>) -> Result<Response, ContractError> {
+        let request_add_verification_key = ExecuteMsg::AddVerificationKey {
+            modulus: HexBinary::from_hex(LOCAL_MODULUS).unwrap(),
+            exponent: HexBinary::from_hex("010001").unwrap(),
+            valid_from,
+            valid_until,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_add_verification_key)
+    }
+
+    #[test]
+    fn add_randomness_success_with_local_verification_key() {
+        let mut deps = default_setup();
+
+        let res = add_local_verification_key(&mut deps, Timestamp::from_seconds(0), None).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","add_verification_key")
+                                .add_attribute("valid_from", "0")
+                                .add_attribute("valid_until", "none")
+                                .add_attribute("change_id", "1")
+                                .add_attribute("effective_time", mock_env().block.time.seconds().to_string())
+                                .add_attribute("owner", CREATOR));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::RevokeVerificationKey{id: 1}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","revoke_verification_key")
+                                .add_attribute("id", "1")
+                                .add_attribute("owner", CREATOR));
+
+        register_bot(&mut deps);
+
+        // payload signed with revoked random org key is rejected
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::RSAVerificationFail{} => {},
+            _ => panic!(),
+        };
+
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(LOCAL_RANDOM_VALUE_TEST),
+            signature: String::from(LOCAL_SIGNATURE_TEST),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap();
+    }
+
+    #[test]
+    fn add_randomness_fail_with_expired_verification_key() {
+        let mut deps = default_setup();
+
+        // random org key is rotated before payload was generated at 2023-02-07 03:05:57
+        let request_set_verification_key_expiry = ExecuteMsg::SetVerificationKeyExpiry {
+            id: 1,
+            valid_until: Some(Timestamp::from_seconds(1672531200)),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_verification_key_expiry).unwrap();
+
+        register_bot(&mut deps);
+
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::VerificationKeyNotFound{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_verification_key_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        let request_add_verification_key = ExecuteMsg::AddVerificationKey {
+            modulus: HexBinary::from_hex(LOCAL_MODULUS).unwrap(),
+            exponent: HexBinary::from_hex("010001").unwrap(),
+            valid_from: Timestamp::from_seconds(0),
+            valid_until: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_add_verification_key).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_verification_key_fail_with_invalid_window() {
+        let mut deps = default_setup();
+
+        let res = add_local_verification_key(&mut deps, Timestamp::from_seconds(100), Some(Timestamp::from_seconds(100))).unwrap_err();
+        match res {
+            ContractError::InvalidVerificationKey{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn verification_key_changes_success_after_delay() {
+        let mut deps = default_setup();
+        set_config_change_delay(&mut deps, 100);
+
+        add_local_verification_key(&mut deps, Timestamp::from_seconds(0), None).unwrap();
+        let request_set_verification_key_expiry = ExecuteMsg::SetVerificationKeyExpiry {
+            id: 1,
+            valid_until: Some(Timestamp::from_seconds(1672531200)),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_verification_key_expiry).unwrap();
+
+        // nothing changes before notice period is over
+        let res: VerificationKeysQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListVerificationKeys{start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.keys.len(), 1);
+        assert_eq!(res.keys[0].valid_until, None);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 2}).unwrap();
+        execute(deps.as_mut(), env, mock_info(USER, &[]), ExecuteMsg::ExecuteConfigChange{change_id: 3}).unwrap();
+
+        let res: VerificationKeysQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListVerificationKeys{start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.keys.len(), 2);
+        assert_eq!(res.keys[0].valid_until, Some(Timestamp::from_seconds(1672531200)));
+        assert_eq!(res.keys[1].modulus, HexBinary::from_hex(LOCAL_MODULUS).unwrap());
+    }
+
+    #[test]
+    fn list_verification_keys_success() {
+        let mut deps = default_setup();
+
+        add_local_verification_key(&mut deps, Timestamp::from_seconds(100), Some(Timestamp::from_seconds(200))).unwrap();
+
+        let res: VerificationKeysQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListVerificationKeys{start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.keys.len(), 2);
+        assert_eq!(res.keys[0].id, 1);
+
+        let res: VerificationKeysQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListVerificationKeys{start_after: Some(1), limit: None}).unwrap()).unwrap();
+        assert_eq!(res.keys, vec![VerificationKey{
+            id: 2,
+            modulus: HexBinary::from_hex(LOCAL_MODULUS).unwrap(),
+            exponent: HexBinary::from_hex("010001").unwrap(),
+            valid_from: Timestamp::from_seconds(100),
+            valid_until: Some(Timestamp::from_seconds(200)),
+            revoked: false,
+        }]);
+    }
+
+    /***** Random Org Parameters *****/
+    // payload of a call with min=0,max=1, each "byte" carries one bit of entropy
+    const LOCAL_LOW_RANGE_RANDOM_VALUE_TEST: &str = r#"{"method":"generateSignedIntegers","hashedApiKey":"elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==","n":32,"min":0,"max":1,"replacement":true,"base":10,"pregeneratedRandomization":null,"data":[1,0,1,1,0,1,0,0,1,1,0,1,0,1,1,0,0,1,0,1,1,1,0,0,1,0,1,0,0,1,1,0],"license":{"type":"developer","text":"Random values licensed strictly for development and testing only","infoUrl":null},"licenseData":null,"userData":null,"ticketData":null,"completionTime":"2023-02-07 03:05:57Z","serialNumber":2}"#;
+    const LOCAL_LOW_RANGE_SIGNATURE_TEST: &str = "mVUbXXVr2s+y2KctnwcCcTbj3/Mrsk4lNDacouEyqaA0T1v4OQNLOcruyhC20ZwhXlKeqpEO/0AmkPt8MKLswqEch7t/54ApZ83MS6awuyPRsAcu27V7Ft73blqvnuHjqMQBoTTrVH8P0qNH9/2ula29xWdyB48oV7ec7SG1Ktk=";
+    // payload of a production api key
+    const LOCAL_BASIC_LICENSE_RANDOM_VALUE_TEST: &str = r#"{"method":"generateSignedIntegers","hashedApiKey":"elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==","n":32,"min":0,"max":255,"replacement":true,"base":10,"pregeneratedRandomization":null,"data":[3,141,59,26,53,58,97,93,238,46,26,43,38,32,79,50,28,84,197,169,39,93,75,105,20,9,74,94,45,92,30,78],"license":{"type":"basic","text":"Random values licensed for use in production","infoUrl":null},"licenseData":null,"userData":null,"ticketData":null,"completionTime":"2023-02-07 03:05:57Z","serialNumber":3}"#;
+    const LOCAL_BASIC_LICENSE_SIGNATURE_TEST: &str = "jGu6P6otgOvxLiezhEZ6sI8MxuEBzKL6kn4I4BSrYJXvXVUJrIkkIES0wrXBKko/k/cvRf3JbVwekDzSVZQ7JhAsMEju+D2llVvalf4dceIs4C5lRMusB0/f4GktSeomhxEjYRRgN92seY3thoxQFOa7QmxZJlFu6I8Bsnq2gLc=";
+
+    fn set_reject_developer_license(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::SetRejectDeveloperLicense{reject: true}).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","set_reject_developer_license")
+                                .add_attribute("reject", "true")
+                                .add_attribute("owner", CREATOR));
+    }
+
+    #[test]
+    fn set_reject_developer_license_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), ExecuteMsg::SetRejectDeveloperLicense{reject: true}).unwrap_err();
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_fail_with_developer_license() {
+        let mut deps = default_setup();
+
+        set_reject_developer_license(&mut deps);
+        let res: ConfigsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap()).unwrap();
+        assert!(res.reject_developer_license);
+
+        register_bot(&mut deps);
+
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::DeveloperLicenseRejected{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_success_with_basic_license() {
+        let mut deps = default_setup();
+
+        set_reject_developer_license(&mut deps);
+        add_local_verification_key(&mut deps, Timestamp::from_seconds(0), None).unwrap();
+        register_bot(&mut deps);
+
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(LOCAL_BASIC_LICENSE_RANDOM_VALUE_TEST),
+            signature: String::from(LOCAL_BASIC_LICENSE_SIGNATURE_TEST),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap();
+    }
+
+    #[test]
+    fn add_randomness_fail_with_low_range_payload() {
+        let mut deps = default_setup();
+
+        add_local_verification_key(&mut deps, Timestamp::from_seconds(0), None).unwrap();
+        register_bot(&mut deps);
+
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(LOCAL_LOW_RANGE_RANDOM_VALUE_TEST),
+            signature: String::from(LOCAL_LOW_RANGE_SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::InvalidRandomOrgParameter{name} => {assert_eq!(name, "max")},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_fail_with_verification_fail() {
+        let mut deps = default_setup();
+
+        //register bot
+        register_bot(&mut deps);
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from("eITMbucgIRih+606JH/zfYDIBqOYbB4VEyjCkLJIteIqMMRMZrFRBPmP4Lm+AXNSr4pl2j5fGBXcBJJUdLb4i1p/o4yI7XMg3B3lxhxbZc0fLQ4oWfPniM7El8T6AzxSgBl+OzPU08A+628j7D88IxaGXk5nzrCOmyYhTElfJNwe7erT2SJu9ydA0bC8OypRxJvfBAq4repxhsYFOG32ZhiTQ60BrjB2cTkgTTsLtBYipvp/sTfMZtUAwZ4wrYmSnBqgAFhM9IvpasrYp/4b2wej4AOKwMD34iipg84+29JwwapRBdWizzUm/TdKMvHUMAnwfyWkGs48mMtVjQstWA6A/gWkQILC5DnWJwF0DG1xOUSWO3lc3ETCDt9kNzO6y43ybYZaTma65w3xlLmuMaJAj1tIRAgHcMIHrlC0nmy9FLKVUf/drjsF5BlKbCIG6mWFuQcG4rNCsLu+3l1DjP5QeJZul9DEREHRtbkPsLCAN/Vxe/M6jieKGEJzoE2FEqeeQZCV5n7ihYVOmcJwvO2e4rBpVuu6/giqB2qd+mNqnwyPoTRn60uZPNpyxzLA+L5VRbzNHIsukQHjAB1wZO7KFomHV0xT8WOHDsTO7QKLE8T5UaEeJZVYFLduj1Eg+b05YvqRV4dW6L6/5oVnHpDEYYsJaS+HeRXrcBS3/Lk="),
+        };
+        
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+
+        match res {
+            ContractError::RSAVerificationFail{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_with_invalid_api_key() {
+        let mut deps = default_setup();
+
+        //register bot
+        let hashed_api_key = String::from("test hashed api key");
+        let moniker = String::from("test bot");
+
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: hashed_api_key.clone(),
+            moniker: moniker.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","register_bot")
+                                .add_attribute("hashed_api_key", hashed_api_key)
+                                .add_attribute("moniker", moniker)
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE))
+                                .add_attribute("status", "active")
+                                .add_attribute("bot_address", BOT));
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from(SIGNATURE_TEST),
+        };
+        
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+
+        match res {
+            ContractError::InvalidApiKey{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_fail_with_reused_serial_number() {
+        let mut deps = default_setup();
+
+        //register bot
+        register_bot(&mut deps);
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness.clone()).unwrap();
+
+        // resubmit the same signed payload
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+
+        match res {
+            ContractError::InvalidSerialNumber{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn add_randomness_fail_with_completion_time_before_pending_commitment() {
+        let mut deps = default_setup();
+
+        //user request randomness after random value was generated
         let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
-            request_id: request_id.clone(),
-            num: 1
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(1675739160),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(600u128, DENOM)), request_hex_randomness).unwrap();
+
+        //register bot
+        register_bot(&mut deps);
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::InvalidCompletionTime{} => {},
+            _ => panic!(),
+        };
+
+        // payload without user data is rejected, bot is not slashed and commitment is kept for another fulfillment
+        let res: Option<BotInfoQuery> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(BOT) }).unwrap()).unwrap();
+        let bot_info = res.unwrap();
+        assert_eq!(bot_info.bonded, Uint128::from(MIN_STAKE));
+        assert!(bot_info.slashes.is_empty());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitment{id: make_commit_id(USER.to_string(), 0u64)}).unwrap();
+        let commitment: Option<CommitmentQuery> = from_binary(&res).unwrap();
+        assert_eq!(commitment.unwrap().status, CommitmentStatus::Pending);
+    }
+
+    #[test]
+    fn add_randomness_fail_with_completion_time_equal_commit_time() {
+        let mut deps = default_setup();
+
+        //user request randomness so that commit time is completion time of random value
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1675739157 - TIME_PER_BLOCK);
+        execute(deps.as_mut(), env, mock_info(USER, &coins(600u128, DENOM)), request_hex_randomness).unwrap();
+
+        //register bot
+        register_bot(&mut deps);
+
+        //add randomness, it can not fulfil commitment made at the same time
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        match res {
+            ContractError::InvalidCompletionTime{} => {},
+            _ => panic!(),
+        };
+    }
+
+    /***** Nois Receive *****/
+    #[test]
+    fn nois_receive_success_with_none_commitment() {
+        let mut deps = default_setup();
+
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: String::from("test callback 1"),
+                randomness: HexBinary::from(&[0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00]),
+            }
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        assert_eq!(res, Response::new().add_attribute("action","nois_receive")
+                                    .add_attribute("message","commitment has been made")
+                                    .add_attribute("nois_proxy_address", String::from(NOIS_PROXY_ADDR)));
+    }   
+
+    
+    #[test]
+    fn nois_receive_success_with_commitment() {
+        let mut deps = default_setup(); 
+
+        // request randomness 
+        request_hex_randomness(&mut deps);
+
+        let nonce: u64 = 0u64;
+        let commit_id = make_commit_id(USER.to_string(), nonce);
+
+        // nois callback
+        let randomness =  [0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00];
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: commit_id.clone(),
+                randomness: HexBinary::from(&randomness),
+            }
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        let sub_messages: Vec<SubMsg> = vec![
+            SubMsg {
+                msg: WasmMsg::Execute {
+                    contract_addr: USER.to_string(),
+                    msg: to_binary(&CallbackExecuteMsg::ReceiveHexRandomness{ 
+                        request_id: String::from("test id 1"), 
+                        randomness: vec![String::from("daebd62c597c8b55f72695073d3172aa641c6ef4aa8876546b5186933ca5fa50")],
+                        payload: None,
+                    }).unwrap(),
+                    funds: vec![],
+                }
+                .into(),
+                id: COMMITMENT_CALLBACK_REPLY_ID_START,
+                gas_limit: Some(CALLBACK_LIMIT_GAS),
+                reply_on: ReplyOn::Always,
+            },
+        ];
+
+        assert_eq!(res, Response::new().add_submessages(sub_messages)
+                            .add_event(Event::new("commitment_fulfilled")
+                                .add_attribute("commitment_id", commit_id.clone())
+                                .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string()))
+                            .add_attribute("job_id", commit_id.clone())
+                            .add_attribute("randomness", hex::encode(randomness))
+                            .add_attribute("action","nois_receive")
+                            .add_attribute("nois_proxy_address", String::from(NOIS_PROXY_ADDR)));
+    } 
+
+    #[test]
+    fn nois_receive_fail_with_unauthorized_receive() {
+        let mut deps = default_setup();
+
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: String::from("test callback 1"),
+                randomness: HexBinary::from(&[0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00]),
+            }
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_nois_receive).unwrap_err();
+
+        match res {
+            ContractError::UnauthorizedReceive{} => {},
+            _ => panic!(),
+        }; 
+    }
+
+    #[test]
+    fn nois_receive_fail_with_invalid_randomness() {
+        let mut deps = default_setup();
+
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: String::from("test callback 1"),
+                randomness: HexBinary::from(&[0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00]),
+            }
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap_err();
+
+        match res {
+            ContractError::InvalidRandomness{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn nois_receive_success_with_callback_address() {
+        let mut deps = default_setup();
+
+        // router contract requests randomness on behalf of game contract
+        let payload = to_binary(&"route 1").unwrap();
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: Some(String::from(GAME)),
+            callback_payload: Some(payload.clone()),
+            callback_gas_limit: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(600u128, DENOM)), request_hex_randomness).unwrap();
+
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: commit_id,
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        assert_eq!(res.messages[0], SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: GAME.to_string(),
+                msg: to_binary(&CallbackExecuteMsg::ReceiveHexRandomness{ 
+                    request_id: String::from("test id 1"), 
+                    randomness: vec![String::from("daebd62c597c8b55f72695073d3172aa641c6ef4aa8876546b5186933ca5fa50")],
+                    payload: Some(payload),
+                }).unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            id: COMMITMENT_CALLBACK_REPLY_ID_START,
+            gas_limit: Some(CALLBACK_LIMIT_GAS),
+            reply_on: ReplyOn::Always,
+        });
+    }
+
+    #[test]
+    fn request_hex_randomness_fail_with_invalid_callback_address() {
+        let mut deps = default_setup();
+
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            delivery_mode: None,
+            callback_address: Some(String::from("")),
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(600u128, DENOM)), request_hex_randomness).unwrap_err();
+
+        match res {
+            ContractError::InvalidAddress{} => {},
+            _ => panic!(),
+        };
+    }
+
+    /***** Exactly-once delivery *****/
+    #[test]
+    fn add_randomness_skip_commitment_fulfilled_by_nois() {
+        let mut deps = default_setup();
+
+        // request randomness
+        request_hex_randomness(&mut deps);
+
+        //register bot
+        register_bot(&mut deps);
+
+        let nonce: u64 = 0u64;
+        let commit_id = make_commit_id(USER.to_string(), nonce);
+
+        // nois callback arrives first
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: commit_id,
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        // random org randomness arrives later, commitment must not be delivered again
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(1675739157),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
+
+        let messages: Vec<SubMsg> = Vec::new();
+        assert_eq!(res, Response::new().add_attribute("action","add_randomness")
+                                .add_attribute("random_value", String::from(RANDOM_VALUE_TEST))
+                                .add_attribute("signature", String::from(SIGNATURE_TEST))
+                                .add_attribute("bot", String::from(BOT))
+                                .add_attribute("reward", Uint128::zero())
+                                .add_submessages(messages));
+    }
+
+    #[test]
+    fn nois_receive_skip_commitment_fulfilled_by_random_org() {
+        let mut deps = default_setup();
+
+        // request randomness
+        request_hex_randomness(&mut deps);
+
+        //register bot
+        register_bot(&mut deps);
+
+        // random org randomness arrives first
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST),
+            signature: String::from(SIGNATURE_TEST),
+        };
+
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 0,
+                time: Timestamp::from_seconds(1675739157),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let nonce: u64 = 0u64;
+        let commit_id = make_commit_id(USER.to_string(), nonce);
+
+        // nois callback arrives later, commitment must not be delivered again
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: commit_id,
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        assert_eq!(res, Response::new().add_attribute("action","nois_receive")
+                                    .add_attribute("message","commitment has been made")
+                                    .add_attribute("nois_proxy_address", String::from(NOIS_PROXY_ADDR)));
+    }
+
+    /***** Reclaim Expired *****/
+    fn reclaim_expired_env() -> Env {
+        Env {
+            block: BlockInfo {
+                height: 4,
+                time: Timestamp::from_seconds(1675739170),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        }
+    }
+
+    fn expired_callback_submsg() -> SubMsg {
+        SubMsg {
+            msg: WasmMsg::Execute {
+                contract_addr: USER.to_string(),
+                msg: to_binary(&CallbackExecuteMsg::ReceiveRandomnessExpired{ 
+                    request_id: String::from("test id 1"), 
+                    payload: None,
+                }).unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            id: EXPIRED_CALLBACK_REPLY_ID,
+            gas_limit: Some(CALLBACK_LIMIT_GAS),
+            reply_on: ReplyOn::Error,
+        }
+    }
+
+    #[test]
+    fn reclaim_expired_success_by_owner() {
+        let mut deps = default_setup();
+
+        // request randomness
+        request_hex_randomness(&mut deps);
+
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![commit_id.clone()],
+        };
+        let res = execute(deps.as_mut(), reclaim_expired_env(), mock_info(USER, &[]), request_reclaim_expired).unwrap();
+
+        let messages: Vec<SubMsg> = vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from(USER),
+                amount: coins(FEE, String::from(DENOM)),
+            }),
+            expired_callback_submsg(),
+        ];
+
+        assert_eq!(res, Response::new().add_submessages(messages)
+                            .add_event(Event::new("commitment_expired")
+                                .add_attribute("commitment_id", commit_id)
+                                .add_attribute("request_id", "test id 1")
+                                .add_attribute("owner", USER)
+                                .add_attribute("refund", Uint128::from(FEE))
+                                .add_attribute("keeper_reward", Uint128::from(0u128))
+                                .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string()))
+                            .add_attribute("action","reclaim_expired")
+                            .add_attribute("keeper_reward", Uint128::from(0u128))
+                            .add_attribute("keeper", USER));
+    }
+
+    #[test]
+    fn reclaim_expired_success_by_keeper() {
+        let mut deps = default_setup();
+
+        // request randomness
+        request_hex_randomness(&mut deps);
+
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![commit_id.clone()],
+        };
+        let res = execute(deps.as_mut(), reclaim_expired_env(), mock_info(KEEPER, &[]), request_reclaim_expired.clone()).unwrap();
+
+        let messages: Vec<SubMsg> = vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from(USER),
+                amount: coins(FEE - KEEPER_REWARD, String::from(DENOM)),
+            }),
+            expired_callback_submsg(),
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from(KEEPER),
+                amount: coins(KEEPER_REWARD, String::from(DENOM)),
+            }),
+        ];
+
+        assert_eq!(res, Response::new().add_submessages(messages)
+                            .add_event(Event::new("commitment_expired")
+                                .add_attribute("commitment_id", commit_id)
+                                .add_attribute("request_id", "test id 1")
+                                .add_attribute("owner", USER)
+                                .add_attribute("refund", Uint128::from(FEE - KEEPER_REWARD))
+                                .add_attribute("keeper_reward", Uint128::from(KEEPER_REWARD))
+                                .add_attribute("callback_gas_limit", CALLBACK_LIMIT_GAS.to_string()))
+                            .add_attribute("action","reclaim_expired")
+                            .add_attribute("keeper_reward", Uint128::from(KEEPER_REWARD))
+                            .add_attribute("keeper", KEEPER));
+
+        // commitment can only be refunded once
+        let res = execute(deps.as_mut(), reclaim_expired_env(), mock_info(KEEPER, &[]), request_reclaim_expired).unwrap();
+        assert!(res.messages.is_empty());
+    }
+
+    #[test]
+    fn reclaim_expired_fail_with_commitment_not_expired() {
+        let mut deps = default_setup();
+
+        // request randomness
+        request_hex_randomness(&mut deps);
+
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![make_commit_id(USER.to_string(), 0u64)],
+        };
+
+        let env: Env = Env {
+            block: BlockInfo {
+                height: 1,
+                time: Timestamp::from_seconds(1675739157),
+                chain_id: "euphoria-2".to_string()
+            },
+            contract: ContractInfo {
+                address: Addr::unchecked(CONTRACT_ADDR)
+            },
+            transaction: None,
+        };
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), request_reclaim_expired).unwrap_err();
+
+        match res {
+            ContractError::CommitmentNotExpired{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn reclaim_expired_fail_with_unknown_commitment() {
+        let mut deps = default_setup();
+
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![String::from("unknown id")],
+        };
+        let res = execute(deps.as_mut(), reclaim_expired_env(), mock_info(USER, &[]), request_reclaim_expired).unwrap_err();
+
+        match res {
+            ContractError::CommitmentNotFound{} => {},
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn nois_receive_skip_refunded_commitment() {
+        let mut deps = default_setup();
+
+        // request randomness
+        request_hex_randomness(&mut deps);
+
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![commit_id.clone()],
+        };
+        execute(deps.as_mut(), reclaim_expired_env(), mock_info(USER, &[]), request_reclaim_expired).unwrap();
+
+        // nois callback arrives after refund, commitment must not be delivered
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: commit_id,
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        let res = execute(deps.as_mut(), reclaim_expired_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        assert_eq!(res, Response::new().add_attribute("action","nois_receive")
+                                    .add_attribute("message","commitment has been made")
+                                    .add_attribute("nois_proxy_address", String::from(NOIS_PROXY_ADDR)));
+    }
+
+    /***** Claim Randomness *****/
+    // request int randomness in pull mode and fulfill it by random org
+    fn fulfill_pull_int_randomness(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: String::from("test id 1"),
+            num: 1,
+            min: 0,
+            max: 255,
+            delivery_mode: Some(DeliveryMode::Pull),
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
+        };
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1675739151);
+        execute(deps.as_mut(), env, mock_info(USER, &coins(600u128, DENOM)), request_int_randomness).unwrap();
+
+        //register bot
+        register_bot(deps);
+
+        //add randomness
+        let request_add_randomness = ExecuteMsg::AddRandomness {
+            random_value: String::from(RANDOM_VALUE_TEST), 
+            signature: String::from(SIGNATURE_TEST),
+        };
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1675739157);
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
+
+        // randomness is kept in state, no callback is sent
+        assert_eq!(res.messages, vec![]);
+    }
+
+    #[test]
+    fn claim_randomness_success() {
+        let mut deps = default_setup();
+
+        fulfill_pull_int_randomness(&mut deps);
+
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRandomnessResult{ commitment_id: commit_id.clone() }).unwrap();
+        assert_eq!(res, to_binary(&Some(DerivedRandomness::Int(vec![208i32]))).unwrap());
+
+        let request_claim_randomness = ExecuteMsg::ClaimRandomness {
+            commitment_id: commit_id.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_claim_randomness.clone()).unwrap();
+        assert_eq!(res, Response::new().set_data(to_binary(&DerivedRandomness::Int(vec![208i32])).unwrap())
+                            .add_attribute("action","claim_randomness")
+                            .add_attribute("commitment_id", commit_id.clone())
+                            .add_attribute("request_id", "test id 1")
+                            .add_attribute("owner", USER));
+
+        // randomness can only be claimed once
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_claim_randomness).unwrap_err();
+        match res {
+            ContractError::RandomnessNotFound{} => {},
+            _ => panic!(),
+        };
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRandomnessResult{ commitment_id: commit_id }).unwrap();
+        assert_eq!(res, to_binary(&None::<DerivedRandomness>).unwrap());
+    }
+
+    #[test]
+    fn claim_randomness_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        fulfill_pull_int_randomness(&mut deps);
+
+        let request_claim_randomness = ExecuteMsg::ClaimRandomness {
+            commitment_id: make_commit_id(USER.to_string(), 0u64),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_claim_randomness).unwrap_err();
+
+        match res {
+            ContractError::Unauthorized{} => {},
+            _ => panic!(),
+        };
+    }
+
+    // QUERY
+
+    fn add_commitments(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, commit_id: String, commit_time: u64, expired_time: u64) {
+        let commitment: Commitment = Commitment {
+            id: commit_id.clone(),
+            request_id: String::from("request id"),
+            owner: Addr::unchecked(String::from(USER)),
+            commit_time: Timestamp::from_seconds(commit_time),
+            expired_time: Timestamp::from_seconds(expired_time),
+            data_request: DataRequest{
+                min: 0,
+                max: 255,
+                num: 32,
+                data_type: String::from("test data type"),
+            },
+            fee: Uint128::from(FEE),
+            delivery_mode: DeliveryMode::Callback,
+            callback_address: Addr::unchecked(USER),
+            callback_payload: None,
+            callback_gas_limit: CALLBACK_LIMIT_GAS,
+        };
+        add_commitment(&mut deps.storage, &commitment).unwrap();
+    }
+
+
+    #[test]
+    fn query_get_number_of_commitments_success() {
+        let mut deps = default_setup();
+
+        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
+        add_commitments(&mut deps, String::from("2"), 0u64, 5u64);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetNumberOfCommitment{}).unwrap();
+
+        assert_eq!(res, to_binary(&NumberOfCommitmentQuery{num: 2}).unwrap());
+    }
+
+    #[test]
+    fn query_get_pending_commitments_success() {
+        let mut deps = default_setup();
+
+        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
+        add_commitments(&mut deps, String::from("2"), 0u64, 5u64);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetPendingCommitments{ limit: 1}).unwrap();
+
+        let commitments: Vec<Commitment> = vec![Commitment {
+            id: String::from("1"),
+            request_id: String::from("request id"),
+            owner: Addr::unchecked(String::from(USER)),
+            commit_time: Timestamp::from_seconds(0u64),
+            expired_time: Timestamp::from_seconds(5u64),
+            data_request: DataRequest{
+                min: 0,
+                max: 255,
+                num: 32,
+                data_type: String::from("test data type"),
+            },
+            fee: Uint128::from(FEE),
+            delivery_mode: DeliveryMode::Callback,
+            callback_address: Addr::unchecked(USER),
+            callback_payload: None,
+            callback_gas_limit: CALLBACK_LIMIT_GAS,
+        }];
+        assert_eq!(res, to_binary(&CommitmentsQuery{commitments}).unwrap());
+    }
+
+    #[test]
+    fn query_get_commitments_success() {
+        let mut deps = default_setup();
+
+        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitments{ limit: 1}).unwrap();
+
+        let commitments: Vec<Commitment> = vec![Commitment {
+            id: String::from("1"),
+            request_id: String::from("request id"),
+            owner: Addr::unchecked(String::from(USER)),
+            commit_time: Timestamp::from_seconds(0u64),
+            expired_time: Timestamp::from_seconds(5u64),
+            data_request: DataRequest{
+                min: 0,
+                max: 255,
+                num: 32,
+                data_type: String::from("test data type"),
+            },
+            fee: Uint128::from(FEE),
+            delivery_mode: DeliveryMode::Callback,
+            callback_address: Addr::unchecked(USER),
+            callback_payload: None,
+            callback_gas_limit: CALLBACK_LIMIT_GAS,
+        }];
+        assert_eq!(res, to_binary(&PendingCommitmentsQuery{commitments}).unwrap());
+    }
+    
+    // commitment made by `request_hex_randomness`
+    fn requested_hex_commitment() -> Commitment {
+        Commitment {
+            id: make_commit_id(USER.to_string(), 0u64),
+            request_id: String::from("test id 1"),
+            owner: Addr::unchecked(USER),
+            commit_time: Timestamp::from_seconds(1675739156),
+            expired_time: Timestamp::from_seconds(1675739161),
+            data_request: DataRequest{
+                min: 0,
+                max: 0,
+                num: 1,
+                data_type: String::from("hex"),
+            },
+            fee: Uint128::from(FEE),
+            delivery_mode: DeliveryMode::Callback,
+            callback_address: Addr::unchecked(USER),
+            callback_payload: None,
+            callback_gas_limit: CALLBACK_LIMIT_GAS,
+        }
+    }
+
+    #[test]
+    fn query_get_commitment_success_with_pending() {
+        let mut deps = default_setup();
+
+        request_hex_randomness(&mut deps);
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1675739157);
+        let res = query(deps.as_ref(), env, QueryMsg::GetCommitment{ id: make_commit_id(USER.to_string(), 0u64) }).unwrap();
+
+        assert_eq!(res, to_binary(&Some(CommitmentQuery{
+            commitment: requested_hex_commitment(),
+            status: CommitmentStatus::Pending,
+        })).unwrap());
+    }
+
+    #[test]
+    fn query_get_commitment_success_with_expired() {
+        let mut deps = default_setup();
+
+        request_hex_randomness(&mut deps);
+
+        let res = query(deps.as_ref(), reclaim_expired_env(), QueryMsg::GetCommitment{ id: make_commit_id(USER.to_string(), 0u64) }).unwrap();
+
+        assert_eq!(res, to_binary(&Some(CommitmentQuery{
+            commitment: requested_hex_commitment(),
+            status: CommitmentStatus::Expired,
+        })).unwrap());
+    }
+
+    #[test]
+    fn query_get_commitment_success_with_refunded() {
+        let mut deps = default_setup();
+
+        request_hex_randomness(&mut deps);
+
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![commit_id.clone()],
+        };
+        execute(deps.as_mut(), reclaim_expired_env(), mock_info(USER, &[]), request_reclaim_expired).unwrap();
+
+        let res = query(deps.as_ref(), reclaim_expired_env(), QueryMsg::GetCommitment{ id: commit_id }).unwrap();
+
+        assert_eq!(res, to_binary(&Some(CommitmentQuery{
+            commitment: requested_hex_commitment(),
+            status: CommitmentStatus::Refunded { time: Timestamp::from_seconds(1675739170) },
+        })).unwrap());
+    }
+
+    #[test]
+    fn query_get_commitment_by_request_success_with_fulfilled() {
+        let mut deps = default_setup();
+
+        request_hex_randomness(&mut deps);
+
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: make_commit_id(USER.to_string(), 0u64),
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitmentByRequest{ 
+            owner: String::from(USER), 
+            request_id: String::from("test id 1"),
+        }).unwrap();
+
+        assert_eq!(res, to_binary(&Some(CommitmentQuery{
+            commitment: requested_hex_commitment(),
+            status: CommitmentStatus::Fulfilled { source: RandomnessSource::Nois, time: mock_env().block.time },
+        })).unwrap());
+    }
+
+    #[test]
+    fn query_get_commitment_by_request_success_with_none() {
+        let mut deps = default_setup();
+
+        request_hex_randomness(&mut deps);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitmentByRequest{ 
+            owner: String::from(USER), 
+            request_id: String::from("test id 2"),
+        }).unwrap();
+
+        assert_eq!(res, to_binary(&None::<CommitmentQuery>).unwrap());
+    }
+
+    #[test]
+    fn get_configs_success() {
+        let deps = default_setup();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap();
+
+        assert_eq!(res, to_binary(&ConfigsQuery{
+            nois_proxy: String::from(NOIS_PROXY_ADDR),
+            nois_fee: Uint128::from(NOIS_FEE),
+            bounty_denom: String::from(DENOM),
+            fee: Uint128::from(FEE),
+            callback_limit_gas: CALLBACK_LIMIT_GAS,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+            time_expired: TIME_EXPIRED,
+            time_per_block: TIME_PER_BLOCK,
+            max_callback_gas: MAX_CALLBACK_GAS,
+            callback_gas_price: Decimal::zero(),
+            config_change_delay: CONFIG_CHANGE_DELAY,
+            min_stake: Uint128::from(MIN_STAKE),
+            unbonding_period: UNBONDING_PERIOD,
+            registration_mode: RegistrationMode::Open,
+            api_key_update_cooldown: API_KEY_UPDATE_COOLDOWN,
+            api_key_grace_period: API_KEY_GRACE_PERIOD,
+            max_api_keys: MAX_API_KEYS,
+            reject_developer_license: false,
+        }).unwrap());
+    }
+
+    #[test]
+    fn get_request_price_success() {
+        let mut deps = default_setup();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRequestPrice{
+            data_type: String::from("hex"),
+            num: 1,
+            delivery_mode: None,
+            callback_gas_limit: None,
+        }).unwrap();
+
+        assert_eq!(res, to_binary(&RequestPriceQuery{
+            funds: coins(FEE + NOIS_FEE, DENOM),
+            fee: Uint128::from(FEE),
+            nois_fee: Uint128::from(NOIS_FEE),
+            callback_gas_limit: CALLBACK_LIMIT_GAS,
+        }).unwrap());
+
+        // pull mode reserves no callback gas
+        set_gas_price(&mut deps, Decimal::permille(1));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRequestPrice{
+            data_type: String::from("int"),
+            num: 1,
+            delivery_mode: Some(DeliveryMode::Pull),
+            callback_gas_limit: Some(500000),
+        }).unwrap();
+
+        assert_eq!(res, to_binary(&RequestPriceQuery{
+            funds: coins(FEE + NOIS_FEE, DENOM),
+            fee: Uint128::from(FEE),
+            nois_fee: Uint128::from(NOIS_FEE),
+            callback_gas_limit: 0,
+        }).unwrap());
+    }
+
+    #[test]
+    fn get_request_price_matches_request_randomness() {
+        let mut deps = default_setup();
+
+        set_gas_price(&mut deps, Decimal::permille(1));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRequestPrice{
+            data_type: String::from("int"),
+            num: 2,
+            delivery_mode: None,
+            callback_gas_limit: Some(500001),
+        }).unwrap();
+        let price: RequestPriceQuery = from_binary(&res).unwrap();
+
+        // gas fee is rounded up
+        assert_eq!(price.funds, coins(1101u128, DENOM));
+
+        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
+            request_id: String::from("test id 1"),
+            min: 0,
+            max: 255,
+            num: 2,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: Some(500001),
         };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &price.funds), request_int_randomness).unwrap();
+    }
+
+    #[test]
+    fn get_request_price_fail_with_invalid_request() {
+        let deps = default_setup();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRequestPrice{
+            data_type: String::from("hex"),
+            num: 0,
+            delivery_mode: None,
+            callback_gas_limit: None,
+        });
+        assert!(res.is_err());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRequestPrice{
+            data_type: String::from("float"),
+            num: 1,
+            delivery_mode: None,
+            callback_gas_limit: None,
+        });
+        assert!(res.is_err());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetRequestPrice{
+            data_type: String::from("hex"),
+            num: 1,
+            delivery_mode: None,
+            callback_gas_limit: Some(MAX_CALLBACK_GAS + 1),
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn get_bot_info_success() { 
+        let mut deps = default_setup();
+
+        let hashed_api_key = String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==");
+        let moniker = String::from("test bot");
 
         let env: Env = Env {
             block: BlockInfo {
                 height: 0,
-                time: Timestamp::from_seconds(1675739151),
+                time: Timestamp::from_seconds(100),
                 chain_id: "euphoria-2".to_string()
             },
             contract: ContractInfo {
@@ -469,47 +3829,48 @@ mod unit_tests {
             },
             transaction: None,
         };
-        let coin: Coin = Coin{
-            denom: "ueaura".to_string(),
-            amount: Uint128::from(600u128),
-        };
-        let res = execute(deps.as_mut(), env, mock_info(USER, &[coin]), request_hex_randomness).unwrap();
-        
-        let nonce: u64 = 0u64;
-        let commit_id = make_commit_id(USER.to_string(), nonce);
-
-        let sub_msg: SubMsg = SubMsg {
-            msg: WasmMsg::Execute {
-                contract_addr: NOIS_PROXY_ADDR.to_string(),
-                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
-                                job_id: commit_id.clone() }).unwrap(),
-                funds: coins(NOIS_FEE, DENOM),
-            }
-            .into(),
-            id: NOIS_CALLBACK_REPLY_ID,
-            gas_limit: None,
-            reply_on: ReplyOn::Always,
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: hashed_api_key.clone(),
+            moniker: moniker.clone(),
         };
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","register_bot")
+                                .add_attribute("hashed_api_key", hashed_api_key.clone())
+                                .add_attribute("moniker", moniker.clone())
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE))
+                                .add_attribute("status", "active")
+                                .add_attribute("bot_address", BOT));
+    
 
-        assert_eq!(res, Response::new().add_submessage(sub_msg)
-                            .add_attribute("action", "request_randomness")
-                            .add_attribute("commitment_id",commit_id)
-                            .add_attribute("request_id",request_id)
-                            .add_attribute("user", String::from(USER)));
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(BOT) }).unwrap();
+        assert_eq!(res, to_binary(&Some(BotInfoQuery{
+            address: String::from(BOT),
+            api_keys: vec![ApiKey{
+                hashed_api_key,
+                added_time: Timestamp::from_seconds(100),
+                valid_until: None,
+            }],
+            moniker,
+            last_update: Timestamp::from_seconds(100),
+            status: BotStatus::Active,
+            bonded: Uint128::from(MIN_STAKE),
+            unbonding: vec![],
+            slashes: vec![],
+            metrics: BotMetrics::default(),
+        })).unwrap());
     }
 
-    fn request_int_randomness(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
-        let request_id: String = String::from("test id 1");
-        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
-            request_id: request_id.clone(),
-            num: 1,
-            min: 0,
-            max: 255,
-        };
+    #[test]
+    fn get_bot_info_success_with_none() { 
+        let mut deps = default_setup();
+
+        let hashed_api_key = String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==");
+        let moniker = String::from("test bot");
+
         let env: Env = Env {
             block: BlockInfo {
                 height: 0,
-                time: Timestamp::from_seconds(1675739151),
+                time: Timestamp::from_seconds(100),
                 chain_id: "euphoria-2".to_string()
             },
             contract: ContractInfo {
@@ -517,696 +3878,794 @@ mod unit_tests {
             },
             transaction: None,
         };
-        let coin: Coin = Coin{
-            denom: "ueaura".to_string(),
-            amount: Uint128::from(600u128),
+        let request_register_bot = ExecuteMsg::RegisterBot {
+            hashed_api_key: hashed_api_key.clone(),
+            moniker: moniker.clone(),
         };
-        let res = execute(deps.as_mut(), env, mock_info(USER, &[coin]), request_int_randomness).unwrap();
+        let res = execute(deps.as_mut(), env, mock_info(BOT, &coins(MIN_STAKE, DENOM)), request_register_bot).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","register_bot")
+                                .add_attribute("hashed_api_key", hashed_api_key.clone())
+                                .add_attribute("moniker", moniker.clone())
+                                .add_attribute("bonded", Uint128::from(MIN_STAKE))
+                                .add_attribute("status", "active")
+                                .add_attribute("bot_address", BOT));
+    
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(USER) }).unwrap();
+        assert_eq!(res, to_binary(&None::<BotInfoQuery>).unwrap());
+    }
 
-        let nonce: u64 = 0u64;
-        let commit_id = make_commit_id(USER.to_string(), nonce);
+    // REPLY
+    #[test]
+    fn reply_success(){
+        let mut deps = default_setup();
+        let res = reply(deps.as_mut(), mock_env(), cosmwasm_std::Reply { id: 1, result: SubMsgResult::Err(String::from(""))}).unwrap();
+        assert_eq!(res, Response::new());
+    }
 
-        let sub_msg: SubMsg = SubMsg {
-            msg: WasmMsg::Execute {
-                contract_addr: NOIS_PROXY_ADDR.to_string(),
-                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
-                                job_id: commit_id.clone() }).unwrap(),
-                funds: coins(NOIS_FEE, DENOM),
+    // deliver hex randomness of `request_hex_randomness` through nois and fail its callback
+    fn fail_hex_randomness_callback(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        request_hex_randomness(deps);
+
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: make_commit_id(USER.to_string(), 0u64),
+                randomness: HexBinary::from(&[0x00;32]),
             }
-            .into(),
-            id: NOIS_CALLBACK_REPLY_ID,
-            gas_limit: None,
-            reply_on: ReplyOn::Always,
         };
-
-        assert_eq!(res, Response::new().add_submessage(sub_msg)
-                                .add_attribute("action", "request_randomness")
-                                .add_attribute("commitment_id",commit_id)
-                                .add_attribute("request_id",request_id)
-                                .add_attribute("user", String::from(USER)));
+        execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+
+        let res = reply(deps.as_mut(), mock_env(), cosmwasm_std::Reply { 
+            id: COMMITMENT_CALLBACK_REPLY_ID_START, 
+            result: SubMsgResult::Err(String::from("out of gas")),
+        }).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","callback_failed")
+                            .add_attribute("commitment_id", make_commit_id(USER.to_string(), 0u64))
+                            .add_attribute("error", "out of gas"));
     }
 
     #[test]
-    fn request_hex_randomness_success() {
+    fn reply_success_with_failed_callback() {
         let mut deps = default_setup();
-        
-        request_hex_randomness(&mut deps);
-    }
 
-    #[test]
-    fn request_int_randomness_success(){
-        let mut deps = default_setup();
+        fail_hex_randomness_callback(&mut deps);
 
-        request_int_randomness(&mut deps);
-    }
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetFailedCallback{ commitment_id: commit_id.clone() }).unwrap();
+        assert_eq!(res, to_binary(&Some(FailedCallback{
+            error: String::from("out of gas"),
+            randomness: DerivedRandomness::Hex(vec![String::from("daebd62c597c8b55f72695073d3172aa641c6ef4aa8876546b5186933ca5fa50")]),
+            seed: HexBinary::from(&[0x00;32]),
+            time: mock_env().block.time,
+        })).unwrap());
 
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitment{ id: commit_id }).unwrap();
+        assert_eq!(res, to_binary(&Some(CommitmentQuery{
+            commitment: requested_hex_commitment(),
+            status: CommitmentStatus::CallbackFailed { source: RandomnessSource::Nois, time: mock_env().block.time },
+        })).unwrap());
+    }
 
     #[test]
-    fn request_int_randomness_success_multi_time() {
+    fn reply_success_with_delivered_callback() {
         let mut deps = default_setup();
 
-        let request_id: String = String::from("test id 1");
-        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
-            request_id: request_id.clone(),
-            num: 1,
-            min: 0,
-            max: 255,
-        };
+        request_hex_randomness(&mut deps);
 
-        let coin: Coin = Coin{
-            denom: "ueaura".to_string(),
-            amount: Uint128::from(600u128),
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: make_commit_id(USER.to_string(), 0u64),
+                randomness: HexBinary::from(&[0x00;32]),
+            }
         };
+        execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
 
-        // first time
-        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap();
+        let res = reply(deps.as_mut(), mock_env(), cosmwasm_std::Reply { 
+            id: COMMITMENT_CALLBACK_REPLY_ID_START, 
+            result: SubMsgResult::Ok(cosmwasm_std::SubMsgResponse { events: vec![], data: None }),
+        }).unwrap();
+        assert_eq!(res, Response::new());
 
-        let nonce: u64 = 0u64;
-        let commit_id = make_commit_id(USER.to_string(), nonce);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetFailedCallback{ commitment_id: make_commit_id(USER.to_string(), 0u64) }).unwrap();
+        assert_eq!(res, to_binary(&None::<FailedCallback>).unwrap());
+    }
 
-        let sub_msg: SubMsg = SubMsg {
-            msg: WasmMsg::Execute {
-                contract_addr: NOIS_PROXY_ADDR.to_string(),
-                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
-                                job_id: commit_id.clone() }).unwrap(),
-                funds: coins(NOIS_FEE, DENOM),
-            }
-            .into(),
-            id: NOIS_CALLBACK_REPLY_ID,
-            gas_limit: None,
-            reply_on: ReplyOn::Always,
-        };
+    /***** Retry Callback *****/
+    #[test]
+    fn retry_callback_success() {
+        let mut deps = default_setup();
 
-        assert_eq!(res, Response::new().add_submessage(sub_msg)
-                            .add_attribute("action", "request_randomness")
-                            .add_attribute("commitment_id",commit_id)
-                            .add_attribute("request_id",request_id)
-                            .add_attribute("user", String::from(USER)));
+        fail_hex_randomness_callback(&mut deps);
 
-        
-        //second time
-        let request_id: String = String::from("test id 2");
-        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
-            request_id: request_id.clone(),
-            num: 1,
-            min: 0,
-            max: 255,
-        };
+        let commit_id = make_commit_id(USER.to_string(), 0u64);
+        let gas_limit = 3000000u64;
 
-        let coin: Coin = Coin{
-            denom: "ueaura".to_string(),
-            amount: Uint128::from(600u128),
+        let request_retry_callback = ExecuteMsg::RetryCallback {
+            commitment_id: commit_id.clone(),
+            gas_limit,
         };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap();
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_retry_callback).unwrap();
 
-        let nonce: u64 = 1u64;
-        let commit_id = make_commit_id(USER.to_string(), nonce);
-
-        let sub_msg: SubMsg = SubMsg {
+        let sub_msg = SubMsg {
             msg: WasmMsg::Execute {
-                contract_addr: NOIS_PROXY_ADDR.to_string(),
-                msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { 
-                                job_id: commit_id.clone() }).unwrap(),
-                funds: coins(NOIS_FEE, DENOM),
+                contract_addr: USER.to_string(),
+                msg: to_binary(&CallbackExecuteMsg::ReceiveHexRandomness{ 
+                    request_id: String::from("test id 1"), 
+                    randomness: vec![String::from("daebd62c597c8b55f72695073d3172aa641c6ef4aa8876546b5186933ca5fa50")],
+                    payload: None,
+                }).unwrap(),
+                funds: vec![],
             }
             .into(),
-            id: NOIS_CALLBACK_REPLY_ID,
-            gas_limit: None,
+            id: COMMITMENT_CALLBACK_REPLY_ID_START + 1,
+            gas_limit: Some(gas_limit),
             reply_on: ReplyOn::Always,
         };
-
         assert_eq!(res, Response::new().add_submessage(sub_msg)
-                        .add_attribute("action", "request_randomness")
-                        .add_attribute("commitment_id",commit_id)
-                        .add_attribute("request_id",request_id)
-                        .add_attribute("user", String::from(USER)));
-
+                            .add_attribute("action","retry_callback")
+                            .add_attribute("commitment_id", commit_id.clone())
+                            .add_attribute("gas_limit", gas_limit.to_string())
+                            .add_attribute("owner", USER));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitment{ id: commit_id }).unwrap();
+        assert_eq!(res, to_binary(&Some(CommitmentQuery{
+            commitment: requested_hex_commitment(),
+            status: CommitmentStatus::Fulfilled { source: RandomnessSource::Nois, time: mock_env().block.time },
+        })).unwrap());
     }
 
     #[test]
-    fn request_int_randomness_fail_with_invalid_num() {
+    fn retry_callback_fail_with_unauthorized() {
         let mut deps = default_setup();
-        
-        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
-            request_id: "test id 1".to_string(),
-            num: 0,
-            min: 0,
-            max: 255,
-        };
 
-        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_int_randomness).unwrap_err();
+        fail_hex_randomness_callback(&mut deps);
+
+        let request_retry_callback = ExecuteMsg::RetryCallback {
+            commitment_id: make_commit_id(USER.to_string(), 0u64),
+            gas_limit: 3000000u64,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_retry_callback).unwrap_err();
 
         match res {
-            ContractError::CustomError{val: v} => {assert_eq!(v, "number of randomness must be in range 1..256".to_string())},
+            ContractError::Unauthorized{} => {},
             _ => panic!(),
         };
     }
 
     #[test]
-    fn request_int_randomness_fail_with_invalid_denom() {
+    fn retry_callback_fail_with_callback_not_failed() {
         let mut deps = default_setup();
 
-        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
-            request_id: "test id 1".to_string(),
-            num: 1,
-            min: 0,
-            max: 255,
-        };
+        request_hex_randomness(&mut deps);
 
-        let coin: Coin = Coin{
-            denom: "eaura".to_string(),
-            amount: Uint128::from(1u128),
+        let request_retry_callback = ExecuteMsg::RetryCallback {
+            commitment_id: make_commit_id(USER.to_string(), 0u64),
+            gas_limit: 3000000u64,
         };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap_err();
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_retry_callback).unwrap_err();
 
         match res {
-            ContractError::CustomError{val: v} => {assert_eq!(v, "Expected denom ueaura".to_string())},
+            ContractError::CallbackNotFailed{} => {},
             _ => panic!(),
         };
     }
 
     #[test]
-    fn request_int_randomness_fail_with_insufficent_fee() {
+    fn reply_success_with_retry_callback_nested_in_callback() {
         let mut deps = default_setup();
 
-        let request_int_randomness = ExecuteMsg::RequestIntRandomness {
-            request_id: "test id 1".to_string(),
+        // first commitment fails its callback
+        fail_hex_randomness_callback(&mut deps);
+        let first_commit_id = make_commit_id(USER.to_string(), 0u64);
+
+        // second commitment is delivered by nois
+        let request_hex_randomness = ExecuteMsg::RequestHexRandomness {
+            request_id: String::from("test id 2"),
             num: 1,
-            min: 0,
-            max: 255,
+            delivery_mode: None,
+            callback_address: None,
+            callback_payload: None,
+            callback_gas_limit: None,
         };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(600u128, DENOM)), request_hex_randomness).unwrap();
+        let second_commit_id = make_commit_id(USER.to_string(), 1u64);
 
-        let coin: Coin = Coin{
-            denom: "ueaura".to_string(),
-            amount: Uint128::from(1u128),
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: second_commit_id.clone(),
+                randomness: HexBinary::from(&[0x00;32]),
+            }
         };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[coin]), request_int_randomness).unwrap_err();
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+        assert_eq!(res.messages[0].id, COMMITMENT_CALLBACK_REPLY_ID_START + 1);
 
-        match res {
-            ContractError::CustomError{val: v} => {assert_eq!(v, "Insufficient fee! required 600ueaura".to_string())},
-            _ => panic!(),
+        // user contract retries the first callback while handling the second one
+        let request_retry_callback = ExecuteMsg::RetryCallback {
+            commitment_id: first_commit_id.clone(),
+            gas_limit: 3000000u64,
         };
-    }
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_retry_callback).unwrap();
+        assert_eq!(res.messages[0].id, COMMITMENT_CALLBACK_REPLY_ID_START + 2);
+
+        // nested retry replies first and fails, then the second callback replies successfully
+        let res = reply(deps.as_mut(), mock_env(), cosmwasm_std::Reply {
+            id: COMMITMENT_CALLBACK_REPLY_ID_START + 2,
+            result: SubMsgResult::Err(String::from("out of gas")),
+        }).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action","callback_failed")
+                            .add_attribute("commitment_id", first_commit_id.clone())
+                            .add_attribute("error", "out of gas"));
+
+        let res = reply(deps.as_mut(), mock_env(), cosmwasm_std::Reply {
+            id: COMMITMENT_CALLBACK_REPLY_ID_START + 1,
+            result: SubMsgResult::Ok(cosmwasm_std::SubMsgResponse { events: vec![], data: None }),
+        }).unwrap();
+        assert_eq!(res, Response::new());
 
-    /***** Add Randomness *****/
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetFailedCallback{ commitment_id: second_commit_id.clone() }).unwrap();
+        assert_eq!(res, to_binary(&None::<FailedCallback>).unwrap());
 
-    const RANDOM_VALUE_TEST: &str = r#"{"method":"generateSignedIntegers","hashedApiKey":"elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==","n":32,"min":0,"max":255,"replacement":true,"base":10,"pregeneratedRandomization":null,"data":[127,12,177,76,70,175,6,221,126,220,251,62,125,122,39,146,236,173,173,240,28,197,116,202,130,36,88,171,55,232,75,86],"license":{"type":"developer","text":"Random values licensed strictly for development and testing only","infoUrl":null},"licenseData":null,"userData":null,"ticketData":null,"completionTime":"2023-02-07 03:05:57Z","serialNumber":489}"#;
-    const SIGNATURE_TEST: &str = "kITMbucgIRih+606JH/zfYDIBqOYbB4VEyjCkLJIteIqMMRMZrFRBPmP4Lm+AXNSr4pl2j5fGBXcBJJUdLb4i1p/o4yI7XMg3B3lxhxbZc0fLQ4oWfPniM7El8T6AzxSgBl+OzPU08A+628j7D88IxaGXk5nzrCOmyYhTElfJNwe7erT2SJu9ydA0bC8OypRxJvfBAq4repxhsYFOG32ZhiTQ60BrjB2cTkgTTsLtBYipvp/sTfMZtUAwZ4wrYmSnBqgAFhM9IvpasrYp/4b2wej4AOKwMD34iipg84+29JwwapRBdWizzUm/TdKMvHUMAnwfyWkGs48mMtVjQstWA6A/gWkQILC5DnWJwF0DG1xOUSWO3lc3ETCDt9kNzO6y43ybYZaTma65w3xlLmuMaJAj1tIRAgHcMIHrlC0nmy9FLKVUf/drjsF5BlKbCIG6mWFuQcG4rNCsLu+3l1DjP5QeJZul9DEREHRtbkPsLCAN/Vxe/M6jieKGEJzoE2FEqeeQZCV5n7ihYVOmcJwvO2e4rBpVuu6/giqB2qd+mNqnwyPoTRn60uZPNpyxzLA+L5VRbzNHIsukQHjAB1wZO7KFomHV0xT8WOHDsTO7QKLE8T5UaEeJZVYFLduj1Eg+b05YvqRV4dW6L6/5oVnHpDEYYsJaS+HeRXrcBS3/Lk=";
+        let res: CommitmentQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetCommitment{ id: first_commit_id }).unwrap()).unwrap();
+        assert_eq!(res.status, CommitmentStatus::CallbackFailed { source: RandomnessSource::Nois, time: mock_env().block.time });
 
-    #[test]
-    fn add_randomness_success_with_none_commitment() {
-        let mut deps = default_setup();
+        let res: CommitmentQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetCommitment{ id: second_commit_id }).unwrap()).unwrap();
+        assert_eq!(res.status, CommitmentStatus::Fulfilled { source: RandomnessSource::Nois, time: mock_env().block.time });
 
-        //register bot
-        register_bot(&mut deps);
+        // a reply can only be matched to its delivery once
+        let res = reply(deps.as_mut(), mock_env(), cosmwasm_std::Reply {
+            id: COMMITMENT_CALLBACK_REPLY_ID_START + 1,
+            result: SubMsgResult::Err(String::from("out of gas")),
+        });
+        assert!(res.is_err());
+    }
+
+    /***** Bot Rewards *****/
+    fn fulfill_hex_randomness_by_bot(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        request_hex_randomness(deps);
+        register_bot(deps);
 
-        //add randomness
         let request_add_randomness = ExecuteMsg::AddRandomness {
-            random_value: String::from(RANDOM_VALUE_TEST), 
+            random_value: String::from(RANDOM_VALUE_TEST),
             signature: String::from(SIGNATURE_TEST),
         };
-        
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap();
-
-        let messages: Vec<SubMsg> = Vec::new();
-        assert_eq!(res, Response::new().add_attribute("action","add_randomness")
-                                .add_attribute("random_value", String::from(RANDOM_VALUE_TEST))
-                                .add_attribute("signature", String::from(SIGNATURE_TEST))
-                                .add_attribute("bot", String::from(BOT))
-                                .add_submessages(messages));
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1675739157);
+        execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
     }
 
     #[test]
-    fn add_randomness_success_with_request_hex_randomness_commitment() {
+    fn add_randomness_success_with_accrued_rewards() {
         let mut deps = default_setup();
 
-        //user request randomness
-        request_hex_randomness(&mut deps);
+        fulfill_hex_randomness_by_bot(&mut deps);
 
-                                
-        //register bot
-        register_bot(&mut deps);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBotRewards{ address: String::from(BOT) }).unwrap();
+        assert_eq!(res, to_binary(&BotRewardsQuery{
+            address: String::from(BOT),
+            denom: String::from(DENOM),
+            amount: Uint128::from(FEE - PROTOCOL_FEE),
+        }).unwrap());
 
-        //add randomness
-        let request_add_randomness = ExecuteMsg::AddRandomness {
-            random_value: String::from(RANDOM_VALUE_TEST), 
-            signature: String::from(SIGNATURE_TEST),
-        };
+        let treasury: TreasuryQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap();
+        assert_eq!(treasury.protocol_fees, Uint128::from(PROTOCOL_FEE));
+        assert_eq!(treasury.bot_rewards, Uint128::from(FEE - PROTOCOL_FEE));
+        assert_eq!(treasury.reserved, Uint128::zero());
+    }
 
-        let env: Env = Env {
-            block: BlockInfo {
-                height: 0,
-                time: Timestamp::from_seconds(1675739157),
-                chain_id: "euphoria-2".to_string()
-            },
-            contract: ContractInfo {
-                address: Addr::unchecked(CONTRACT_ADDR)
-            },
-            transaction: None,
-        };
-        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
+    #[test]
+    fn add_randomness_success_with_metrics() {
+        let mut deps = default_setup();
 
-        let mut messages: Vec<SubMsg> = Vec::new();
+        fulfill_hex_randomness_by_bot(&mut deps);
 
-        messages.push(SubMsg {
-            msg: WasmMsg::Execute {
-                contract_addr: USER.to_string(),
-                msg: to_binary(&CallbackExecuteMsg::ReceiveHexRandomness{ 
-                    request_id: String::from("test id 1"), 
-                    randomness: vec![String::from("308fb245ab064a15992c03d944baa9e0b9cb253f11f39a697beb021bcb863d9f")],
-                }).unwrap(),
-                funds: vec![],
-            }
-            .into(),
-            id: COMMITMENT_CALLBACK_REPLY_ID,
-            gas_limit: Some(CALLBACK_LIMIT_GAS),
-            reply_on: ReplyOn::Always,
+        assert_eq!(query_bot_info(&deps).metrics, BotMetrics{
+            fulfilled_commitments: 1,
+            submitted_payloads: 1,
+            total_bounty: Uint128::from(FEE - PROTOCOL_FEE),
+            last_fulfillment: Some(Timestamp::from_seconds(1675739157)),
         });
-
-        messages.push(SubMsg::new(BankMsg::Send {
-            to_address: String::from(BOT),
-            amount: coins(FEE, String::from(DENOM)),
-        }));
-
-        assert_eq!(res, Response::new().add_attribute("action","add_randomness")
-                                .add_attribute("random_value", String::from(RANDOM_VALUE_TEST))
-                                .add_attribute("signature", String::from(SIGNATURE_TEST))
-                                .add_attribute("bot", String::from(BOT))
-                                .add_submessages(messages));
     }
 
     #[test]
-    fn add_randomness_success_with_request_int_randomness_commitment() {
+    fn claim_rewards_success() {
         let mut deps = default_setup();
 
-        //user request randomness
-        request_int_randomness(&mut deps);
-
-        //register bot
-        register_bot(&mut deps);
-
-        //add randomness
-        let request_add_randomness = ExecuteMsg::AddRandomness {
-            random_value: String::from(RANDOM_VALUE_TEST), 
-            signature: String::from(SIGNATURE_TEST),
-        };
+        fulfill_hex_randomness_by_bot(&mut deps);
 
-        let env: Env = Env {
-            block: BlockInfo {
-                height: 0,
-                time: Timestamp::from_seconds(1675739157),
-                chain_id: "euphoria-2".to_string()
-            },
-            contract: ContractInfo {
-                address: Addr::unchecked(CONTRACT_ADDR)
-            },
-            transaction: None,
+        let request_claim_rewards = ExecuteMsg::ClaimRewards {
+            recipient: None,
         };
-        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_add_randomness).unwrap();
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_claim_rewards).unwrap();
+
+        assert_eq!(res, Response::new().add_message(BankMsg::Send {
+                                    to_address: String::from(BOT),
+                                    amount: coins(FEE - PROTOCOL_FEE, DENOM),
+                                })
+                                .add_attribute("action", "claim_rewards")
+                                .add_attribute("amount", Uint128::from(FEE - PROTOCOL_FEE))
+                                .add_attribute("recipient", BOT)
+                                .add_attribute("bot", BOT));
+
+        let res: BotRewardsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetBotRewards{ address: String::from(BOT) }).unwrap()).unwrap();
+        assert_eq!(res.amount, Uint128::zero());
+
+        let treasury: TreasuryQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap();
+        assert_eq!(treasury.bot_rewards, Uint128::zero());
+    }
 
-        let mut messages: Vec<SubMsg> = Vec::new();
+    #[test]
+    fn claim_rewards_success_with_recipient() {
+        let mut deps = default_setup();
 
-        messages.push(SubMsg {
-            msg: WasmMsg::Execute {
-                contract_addr: USER.to_string(),
-                msg: to_binary(&CallbackExecuteMsg::ReceiveIntRandomness{ 
-                    request_id: String::from("test id 1"), 
-                    randomness: vec![208i32],
-                }).unwrap(),
-                funds: vec![],
-            }
-            .into(),
-            id: COMMITMENT_CALLBACK_REPLY_ID,
-            gas_limit: Some(CALLBACK_LIMIT_GAS),
-            reply_on: ReplyOn::Always,
-        });
+        fulfill_hex_randomness_by_bot(&mut deps);
 
-        messages.push(SubMsg::new(BankMsg::Send {
-            to_address: String::from(BOT),
-            amount: coins(FEE, String::from(DENOM)),
-        }));
+        let request_claim_rewards = ExecuteMsg::ClaimRewards {
+            recipient: Some(String::from(OPERATOR)),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_claim_rewards).unwrap();
 
-        assert_eq!(res, Response::new().add_attribute("action","add_randomness")
-                                .add_attribute("random_value", String::from(RANDOM_VALUE_TEST))
-                                .add_attribute("signature", String::from(SIGNATURE_TEST))
-                                .add_attribute("bot", String::from(BOT))
-                                .add_submessages(messages));
+        assert_eq!(res.messages, vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from(OPERATOR),
+            amount: coins(FEE - PROTOCOL_FEE, DENOM),
+        })]);
     }
 
     #[test]
-    fn add_randomness_fail_with_bot_not_register() {
+    fn claim_rewards_fail_with_no_rewards() {
         let mut deps = default_setup();
 
-        //add randomness
-        let request_add_randomness = ExecuteMsg::AddRandomness {
-            random_value: String::from(RANDOM_VALUE_TEST), 
-            signature: String::from(SIGNATURE_TEST),
+        fulfill_hex_randomness_by_bot(&mut deps);
+
+        let request_claim_rewards = ExecuteMsg::ClaimRewards {
+            recipient: None,
         };
-        
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+        execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_claim_rewards.clone()).unwrap();
+
+        // rewards can only be claimed once
+        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_claim_rewards).unwrap_err();
         match res {
-            ContractError::UnregisteredAddress{} => {},
+            ContractError::NoRewards{} => {},
             _ => panic!(),
         };
     }
 
-    #[test]
-    fn add_randomness_fail_with_verification_fail() {
-        let mut deps = default_setup();
-
-        //register bot
-        register_bot(&mut deps);
+    /***** Treasury *****/
+    fn query_treasury(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> TreasuryQuery {
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap()
+    }
 
-        //add randomness
-        let request_add_randomness = ExecuteMsg::AddRandomness {
-            random_value: String::from(RANDOM_VALUE_TEST), 
-            signature: String::from("eITMbucgIRih+606JH/zfYDIBqOYbB4VEyjCkLJIteIqMMRMZrFRBPmP4Lm+AXNSr4pl2j5fGBXcBJJUdLb4i1p/o4yI7XMg3B3lxhxbZc0fLQ4oWfPniM7El8T6AzxSgBl+OzPU08A+628j7D88IxaGXk5nzrCOmyYhTElfJNwe7erT2SJu9ydA0bC8OypRxJvfBAq4repxhsYFOG32ZhiTQ60BrjB2cTkgTTsLtBYipvp/sTfMZtUAwZ4wrYmSnBqgAFhM9IvpasrYp/4b2wej4AOKwMD34iipg84+29JwwapRBdWizzUm/TdKMvHUMAnwfyWkGs48mMtVjQstWA6A/gWkQILC5DnWJwF0DG1xOUSWO3lc3ETCDt9kNzO6y43ybYZaTma65w3xlLmuMaJAj1tIRAgHcMIHrlC0nmy9FLKVUf/drjsF5BlKbCIG6mWFuQcG4rNCsLu+3l1DjP5QeJZul9DEREHRtbkPsLCAN/Vxe/M6jieKGEJzoE2FEqeeQZCV5n7ihYVOmcJwvO2e4rBpVuu6/giqB2qd+mNqnwyPoTRn60uZPNpyxzLA+L5VRbzNHIsukQHjAB1wZO7KFomHV0xT8WOHDsTO7QKLE8T5UaEeJZVYFLduj1Eg+b05YvqRV4dW6L6/5oVnHpDEYYsJaS+HeRXrcBS3/Lk="),
-        };
-        
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
+    fn nois_receive_hex_randomness(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        request_hex_randomness(deps);
 
-        match res {
-            ContractError::RSAVerificationFail{} => {},
-            _ => panic!(),
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: make_commit_id(USER.to_string(), 0u64),
+                randomness: HexBinary::from(&[0x00;32]),
+            }
         };
+        execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
     }
 
     #[test]
-    fn add_randomness_with_invalid_api_key() {
+    fn treasury_success_with_pending_commitment() {
         let mut deps = default_setup();
 
-        //register bot
-        let hashed_api_key = String::from("test hashed api key");
-        let moniker = String::from("test bot");
+        request_hex_randomness(&mut deps);
 
-        let request_register_bot = ExecuteMsg::RegisterBot {
-            hashed_api_key: hashed_api_key.clone(),
-            moniker: moniker.clone(),
-        };
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_register_bot).unwrap();
-        assert_eq!(res, Response::new().add_attribute("action","register_bot")
-                                .add_attribute("hashed_api_key", hashed_api_key)
-                                .add_attribute("moniker", moniker)
-                                .add_attribute("bot_address", BOT));
+        // nois fee has been forwarded to proxy, someone sends extra funds to contract directly
+        deps.querier.update_balance(mock_env().contract.address, coins(FEE + 100u128, DENOM));
+
+        assert_eq!(query_treasury(&deps), TreasuryQuery {
+            denom: String::from(DENOM),
+            protocol_fees: Uint128::zero(),
+            bot_rewards: Uint128::zero(),
+            reserved: Uint128::from(FEE),
+            staked: Uint128::zero(),
+            balance: Uint128::from(FEE + 100u128),
+            unaccounted: Uint128::from(100u128),
+        });
+    }
 
-        //add randomness
-        let request_add_randomness = ExecuteMsg::AddRandomness {
-            random_value: String::from(RANDOM_VALUE_TEST), 
-            signature: String::from(SIGNATURE_TEST),
+    #[test]
+    fn set_configs_fail_with_treasury_not_empty() {
+        let mut deps = default_setup();
+
+        let request_set_configs = ExecuteMsg::SetConfigs{
+            bounty_denom: String::from("uaura"),
+            fee: Uint128::from(FEE),
+            callback_limit_gas: CALLBACK_LIMIT_GAS,
+            max_callback: MAX_CALLBACK,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
         };
-        
-        let res = execute(deps.as_mut(), mock_env(), mock_info(BOT, &[]), request_add_randomness).unwrap_err();
 
+        // fee of pending commitment is held in current bounty denom
+        request_hex_randomness(&mut deps);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_configs.clone()).unwrap_err();
         match res {
-            ContractError::InvalidApiKey{} => {},
+            ContractError::TreasuryNotEmpty{} => {},
             _ => panic!(),
         };
+
+        // denom can change once every bucket has been paid out
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired{
+            commitment_ids: vec![make_commit_id(USER.to_string(), 0u64)],
+        };
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1675739151 + TIME_PER_BLOCK + TIME_EXPIRED + 1);
+        execute(deps.as_mut(), env, mock_info(USER, &[]), request_reclaim_expired).unwrap();
+
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_set_configs).unwrap();
+        let res: ConfigsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap()).unwrap();
+        assert_eq!(res.bounty_denom, String::from("uaura"));
+    }
+
+    #[test]
+    fn treasury_success_with_nois_fulfillment() {
+        let mut deps = default_setup();
+
+        nois_receive_hex_randomness(&mut deps);
+
+        // owner takes fulfiller share of nois fulfillment as rewards
+        let treasury = query_treasury(&deps);
+        assert_eq!(treasury.protocol_fees, Uint128::from(PROTOCOL_FEE));
+        assert_eq!(treasury.bot_rewards, Uint128::from(FEE - PROTOCOL_FEE));
+        assert_eq!(treasury.reserved, Uint128::zero());
+
+        let res: BotRewardsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetBotRewards{ address: String::from(CREATOR) }).unwrap()).unwrap();
+        assert_eq!(res.amount, Uint128::from(FEE - PROTOCOL_FEE));
     }
 
-    /***** Nois Receive *****/
     #[test]
-    fn nois_receive_success_with_none_commitment() {
+    fn treasury_success_with_nois_fulfillment_after_renounce() {
         let mut deps = default_setup();
 
-        let request_nois_receive = ExecuteMsg::NoisReceive {
-            callback: NoisCallback{
-                job_id: String::from("test callback 1"),
-                randomness: HexBinary::from(&[0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00]),
-            }
-        };
+        execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), ExecuteMsg::RenounceOwnership{}).unwrap();
 
-        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+        nois_receive_hex_randomness(&mut deps);
 
-        assert_eq!(res, Response::new().add_attribute("action","nois_receive")
-                                    .add_attribute("message","commitment has been made")
-                                    .add_attribute("nois_proxy_address", String::from(NOIS_PROXY_ADDR)));
-    }   
+        // nobody runs nois fulfillment anymore, so treasury collects the whole fee
+        let treasury = query_treasury(&deps);
+        assert_eq!(treasury.protocol_fees, Uint128::from(FEE));
+        assert_eq!(treasury.bot_rewards, Uint128::zero());
+    }
 
-    
     #[test]
-    fn nois_receive_success_with_commitment() {
-        let mut deps = default_setup(); 
+    fn treasury_success_with_refund() {
+        let mut deps = default_setup();
 
-        // request randomness 
         request_hex_randomness(&mut deps);
 
-        let nonce: u64 = 0u64;
-        let commit_id = make_commit_id(USER.to_string(), nonce);
-
-        // nois callback
-        let randomness =  [0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00];
-        let request_nois_receive = ExecuteMsg::NoisReceive {
-            callback: NoisCallback{
-                job_id: commit_id.clone(),
-                randomness: HexBinary::from(&randomness),
-            }
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![make_commit_id(USER.to_string(), 0u64)],
         };
+        execute(deps.as_mut(), reclaim_expired_env(), mock_info(USER, &[]), request_reclaim_expired).unwrap();
 
-        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+        // refunded fee is released without being collected
+        let treasury = query_treasury(&deps);
+        assert_eq!(treasury.protocol_fees, Uint128::zero());
+        assert_eq!(treasury.reserved, Uint128::zero());
+    }
 
-        let mut sub_messages: Vec<SubMsg> = Vec::new(); 
-        sub_messages.push(SubMsg {
-            msg: WasmMsg::Execute {
-                contract_addr: USER.to_string(),
-                msg: to_binary(&CallbackExecuteMsg::ReceiveHexRandomness{ 
-                    request_id: String::from("test id 1"), 
-                    randomness: vec![String::from("daebd62c597c8b55f72695073d3172aa641c6ef4aa8876546b5186933ca5fa50")],
-                }).unwrap(),
-                funds: vec![],
-            }
-            .into(),
-            id: COMMITMENT_CALLBACK_REPLY_ID,
-            gas_limit: Some(CALLBACK_LIMIT_GAS),
-            reply_on: ReplyOn::Always,
-        });
+    #[test]
+    fn withdraw_success() {
+        let mut deps = default_setup();
+        grant_role(&mut deps, Role::Treasurer, OPERATOR);
 
-        // send bounty to contract owner
-        sub_messages.push(SubMsg::new(BankMsg::Send {
-            to_address: String::from(CREATOR),
-            amount: coins(FEE, String::from(DENOM)),
-        }));
+        nois_receive_hex_randomness(&mut deps);
 
-        assert_eq!(res, Response::new().add_submessages(sub_messages)
-                            .add_attribute("job_id", commit_id.clone())
-                            .add_attribute("randomness", hex::encode(randomness))
-                            .add_attribute("action","nois_receive")
-                            .add_attribute("nois_proxy_address", String::from(NOIS_PROXY_ADDR)));
-    } 
+        let request_withdraw = ExecuteMsg::Withdraw {
+            amount: Uint128::from(50u128),
+            recipient: String::from(GAME),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OPERATOR, &[]), request_withdraw).unwrap();
+
+        assert_eq!(res, Response::new().add_message(BankMsg::Send {
+                                    to_address: String::from(GAME),
+                                    amount: coins(50u128, DENOM),
+                                })
+                                .add_attribute("action", "withdraw")
+                                .add_attribute("amount", Uint128::from(50u128))
+                                .add_attribute("recipient", GAME)
+                                .add_attribute("treasurer", OPERATOR));
+
+        assert_eq!(query_treasury(&deps).protocol_fees, Uint128::from(PROTOCOL_FEE - 50u128));
+    }
 
     #[test]
-    fn nois_receive_fail_with_unauthorized_receive() {
+    fn withdraw_fail_with_reserved_funds() {
         let mut deps = default_setup();
 
-        let request_nois_receive = ExecuteMsg::NoisReceive {
-            callback: NoisCallback{
-                job_id: String::from("test callback 1"),
-                randomness: HexBinary::from(&[0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00]),
-            }
-        };
+        // fee of pending commitment is reserved and can not be withdrawn
+        request_hex_randomness(&mut deps);
 
-        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_nois_receive).unwrap_err();
+        let request_withdraw = ExecuteMsg::Withdraw {
+            amount: Uint128::from(FEE),
+            recipient: String::from(CREATOR),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), request_withdraw).unwrap_err();
 
         match res {
-            ContractError::UnauthorizedReceive{} => {},
+            ContractError::InsufficientTreasury{} => {},
             _ => panic!(),
-        }; 
+        };
     }
 
     #[test]
-    fn nois_receive_fail_with_invalid_randomness() {
+    fn withdraw_fail_with_unauthorized() {
         let mut deps = default_setup();
 
-        let request_nois_receive = ExecuteMsg::NoisReceive {
-            callback: NoisCallback{
-                job_id: String::from("test callback 1"),
-                randomness: HexBinary::from(&[0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00]),
-            }
-        };
+        nois_receive_hex_randomness(&mut deps);
 
-        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap_err();
+        let request_withdraw = ExecuteMsg::Withdraw {
+            amount: Uint128::from(FEE),
+            recipient: String::from(USER),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_withdraw).unwrap_err();
 
         match res {
-            ContractError::InvalidRandomness{} => {},
+            ContractError::Unauthorized{} => {},
             _ => panic!(),
-        }; 
+        };
     }
 
-    // QUERY
-
-    fn add_commitments(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, commit_id: String, commit_time: u64, expired_time: u64) {
-        let commitment: Commitment = Commitment {
-            id: commit_id.clone(),
-            request_id: String::from("request id"),
-            owner: Addr::unchecked(String::from(USER)),
+    /***** Migrate *****/
+    fn legacy_commitment(nonce: u64, commit_time: u64) -> v0_1_0::Commitment {
+        v0_1_0::Commitment {
+            id: make_commit_id(USER.to_string(), nonce),
+            request_id: format!("test id {}", nonce),
+            owner: Addr::unchecked(USER),
             commit_time: Timestamp::from_seconds(commit_time),
-            expired_time: Timestamp::from_seconds(expired_time),
-            data_request: DataRequest{
+            expired_time: Timestamp::from_seconds(commit_time + TIME_EXPIRED),
+            data_request: DataRequest {
                 min: 0,
-                max: 255,
-                num: 32,
-                data_type: String::from("test data type"),
+                max: 0,
+                num: 1,
+                data_type: String::from("hex"),
             },
-        };
-        COMMITMENTS.push_front(&mut deps.storage, &commitment).unwrap();
-        PENDING_COMMITMENTS.save(&mut deps.storage, commit_id, &commitment).unwrap();
+        }
     }
 
+    // state of an instance deployed at version 0.1.0
+    fn v0_1_0_setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let storage = deps.as_mut().storage;
 
-    #[test]
-    fn query_get_number_of_commitments_success() {
-        let mut deps = default_setup();
-
-        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
-        add_commitments(&mut deps, String::from("2"), 0u64, 5u64);
-
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetNumberOfCommitment{}).unwrap();
+        set_contract_version(storage, "crates.io:aurand", "0.1.0").unwrap();
+        v0_1_0::CONFIGS.save(storage, &v0_1_0::Configs {
+            bounty_denom: String::from(DENOM),
+            fee: Uint128::from(FEE),
+            callback_limit_gas: CALLBACK_LIMIT_GAS,
+            max_callback: MAX_CALLBACK,
+        }).unwrap();
+        NOIS_CONFIGS.save(storage, &NoisConfigs {
+            nois_proxy: Addr::unchecked(NOIS_PROXY_ADDR),
+            nois_fee: Uint128::from(NOIS_FEE),
+        }).unwrap();
+        TIME_CONFIGS.save(storage, &TimeConfigs {
+            time_expired: TIME_EXPIRED,
+            time_per_block: TIME_PER_BLOCK,
+        }).unwrap();
+        OWNER.save(storage, &Addr::unchecked(CREATOR)).unwrap();
+        v0_1_0::BOTS.save(storage, Addr::unchecked(BOT), &v0_1_0::Bot {
+            address: Addr::unchecked(BOT),
+            hashed_api_key: String::from("test hashed api key"),
+            moniker: String::from("test bot"),
+            last_update: Timestamp::from_seconds(100),
+        }).unwrap();
 
-        assert_eq!(res, to_binary(&NumberOfCommitmentQuery{num: 2}).unwrap());
-    }
+        // commitment 0 was fulfilled by nois, it is only left in queue
+        let fulfilled = legacy_commitment(0, 100);
+        v0_1_0::COMMITMENTS.push_front(storage, &fulfilled).unwrap();
 
-    #[test]
-    fn query_get_pending_commitments_success() {
-        let mut deps = default_setup();
+        // commitment 1 expired and was popped from queue by random org, it is only left in pending map
+        let expired = legacy_commitment(1, 200);
+        v0_1_0::PENDING_COMMITMENTS.save(storage, expired.id.clone(), &expired).unwrap();
 
-        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
-        add_commitments(&mut deps, String::from("2"), 0u64, 5u64);
+        // commitment 2 is still waiting for randomness
+        let pending = legacy_commitment(2, 1675739156);
+        v0_1_0::COMMITMENTS.push_front(storage, &pending).unwrap();
+        v0_1_0::PENDING_COMMITMENTS.save(storage, pending.id.clone(), &pending).unwrap();
 
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetPendingCommitments{ limit: 1}).unwrap();
+        deps
+    }
 
-        let mut commitments: Vec<Commitment> = Vec::new();
-        commitments.push(Commitment {
-            id: String::from("1"),
-            request_id: String::from("request id"),
-            owner: Addr::unchecked(String::from(USER)),
-            commit_time: Timestamp::from_seconds(0u64),
-            expired_time: Timestamp::from_seconds(5u64),
-            data_request: DataRequest{
-                min: 0,
-                max: 255,
-                num: 32,
-                data_type: String::from("test data type"),
-            },
-        });
-        assert_eq!(res, to_binary(&CommitmentsQuery{commitments}).unwrap());
+    fn migrate_msg() -> MigrateMsg {
+        MigrateMsg {
+            keeper_reward: Some(Uint128::from(KEEPER_REWARD)),
+            max_callback_gas: None,
+            callback_gas_price: None,
+            protocol_fee_bps: None,
+            min_stake: None,
+            unbonding_period: None,
+            api_key_update_cooldown: None,
+            api_key_grace_period: None,
+            max_api_keys: None,
+            reject_developer_license: None,
+        }
     }
 
     #[test]
-    fn query_get_commitments_success() {
-        let mut deps = default_setup();
-
-        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
+    fn migrate_from_v0_1_0_success() {
+        let mut deps = v0_1_0_setup();
 
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitments{ limit: 1}).unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), migrate_msg()).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action", "migrate")
+                                .add_attribute("from_version", "0.1.0")
+                                .add_attribute("to_version", env!("CARGO_PKG_VERSION"))
+                                .add_attribute("migrated_commitments", "2"));
 
-        let mut commitments: Vec<Commitment> = Vec::new();
-        commitments.push(Commitment {
-            id: String::from("1"),
-            request_id: String::from("request id"),
-            owner: Addr::unchecked(String::from(USER)),
-            commit_time: Timestamp::from_seconds(0u64),
-            expired_time: Timestamp::from_seconds(5u64),
-            data_request: DataRequest{
-                min: 0,
-                max: 255,
-                num: 32,
-                data_type: String::from("test data type"),
-            },
-        });
-        assert_eq!(res, to_binary(&PendingCommitmentsQuery{commitments}).unwrap());
-    }
-    
-    #[test]
-    fn get_configs_success() {
-        let deps = default_setup();
+        let version = get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
 
         let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfigs{}).unwrap();
-
         assert_eq!(res, to_binary(&ConfigsQuery{
             nois_proxy: String::from(NOIS_PROXY_ADDR),
             nois_fee: Uint128::from(NOIS_FEE),
             bounty_denom: String::from(DENOM),
             fee: Uint128::from(FEE),
             callback_limit_gas: CALLBACK_LIMIT_GAS,
+            keeper_reward: Uint128::from(KEEPER_REWARD),
+            protocol_fee_bps: 0,
             time_expired: TIME_EXPIRED,
             time_per_block: TIME_PER_BLOCK,
+            max_callback_gas: CALLBACK_LIMIT_GAS,
+            callback_gas_price: Decimal::zero(),
+            config_change_delay: CONFIG_CHANGE_DELAY,
+            min_stake: Uint128::zero(),
+            unbonding_period: 0,
+            registration_mode: RegistrationMode::Open,
+            api_key_update_cooldown: TIME_PER_BLOCK + TIME_EXPIRED,
+            api_key_grace_period: TIME_PER_BLOCK + TIME_EXPIRED,
+            max_api_keys: 3,
+            reject_developer_license: false,
         }).unwrap());
+
+        // legacy bot keeps its api key and is listed as active
+        let res: BotsQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListBots{status: Some(BotStatus::Active), start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.bots.len(), 1);
+        let bot = BOTS.load(deps.as_ref().storage, Addr::unchecked(BOT)).unwrap();
+        assert_eq!(bot.status, BotStatus::Active);
+        assert_eq!(bot.api_keys, vec![ApiKey{
+            hashed_api_key: String::from("test hashed api key"),
+            added_time: Timestamp::from_seconds(100),
+            valid_until: None,
+        }]);
+
+        // random org key compiled into 0.1.0 is installed
+        let res: VerificationKeysQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ListVerificationKeys{start_after: None, limit: None}).unwrap()).unwrap();
+        assert_eq!(res.keys.len(), 1);
+
+        // only still pending commitment is kept in queue
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetNumberOfCommitment{}).unwrap();
+        assert_eq!(res, to_binary(&NumberOfCommitmentQuery{num: 1}).unwrap());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitment{id: make_commit_id(USER.to_string(), 0u64)}).unwrap();
+        assert_eq!(res, to_binary(&None::<CommitmentQuery>).unwrap());
+
+        let pending_id = make_commit_id(USER.to_string(), 2u64);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetCommitmentByRequest{
+            owner: String::from(USER),
+            request_id: String::from("test id 2"),
+        }).unwrap();
+        assert_eq!(res, to_binary(&Some(CommitmentQuery{
+            commitment: Commitment {
+                id: pending_id.clone(),
+                request_id: String::from("test id 2"),
+                owner: Addr::unchecked(USER),
+                commit_time: Timestamp::from_seconds(1675739156),
+                expired_time: Timestamp::from_seconds(1675739161),
+                data_request: DataRequest {
+                    min: 0,
+                    max: 0,
+                    num: 1,
+                    data_type: String::from("hex"),
+                },
+                fee: Uint128::from(FEE),
+                delivery_mode: DeliveryMode::Callback,
+                callback_address: Addr::unchecked(USER),
+                callback_payload: None,
+                callback_gas_limit: CALLBACK_LIMIT_GAS,
+            },
+            status: CommitmentStatus::Pending,
+        })).unwrap());
+
+        // fees of migrated pending commitments are reserved
+        let treasury: TreasuryQuery = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetTreasury{}).unwrap()).unwrap();
+        assert_eq!(treasury.reserved, Uint128::from(2 * FEE));
+
+        // backfilled commitment can be fulfilled by nois
+        let request_nois_receive = ExecuteMsg::NoisReceive {
+            callback: NoisCallback{
+                job_id: pending_id,
+                randomness: HexBinary::from(&[0x00;32]),
+            }
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(NOIS_PROXY_ADDR, &[]), request_nois_receive).unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        // expired commitment that has never been delivered can be refunded
+        let request_reclaim_expired = ExecuteMsg::ReclaimExpired {
+            commitment_ids: vec![make_commit_id(USER.to_string(), 1u64)],
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), request_reclaim_expired).unwrap();
+        assert_eq!(res.messages[0], SubMsg::new(BankMsg::Send {
+            to_address: String::from(USER),
+            amount: coins(FEE, String::from(DENOM)),
+        }));
     }
 
     #[test]
-    fn get_bot_info_success() { 
-        let mut deps = default_setup();
-
-        let hashed_api_key = String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==");
-        let moniker = String::from("test bot");
+    fn migrate_from_v0_1_0_fail_with_invalid_fee_split() {
+        let mut deps = v0_1_0_setup();
 
-        let env: Env = Env {
-            block: BlockInfo {
-                height: 0,
-                time: Timestamp::from_seconds(100),
-                chain_id: "euphoria-2".to_string()
-            },
-            contract: ContractInfo {
-                address: Addr::unchecked(CONTRACT_ADDR)
-            },
-            transaction: None,
+        let mut msg = migrate_msg();
+        msg.protocol_fee_bps = Some(10001);
+        let res = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+        match res {
+            ContractError::InvalidFeeSplit{} => {},
+            _ => panic!(),
         };
-        let request_register_bot = ExecuteMsg::RegisterBot {
-            hashed_api_key: hashed_api_key.clone(),
-            moniker: moniker.clone(),
+    }
+
+    #[test]
+    fn migrate_from_v0_1_0_fail_with_zero_max_api_keys() {
+        let mut deps = v0_1_0_setup();
+
+        let mut msg = migrate_msg();
+        msg.max_api_keys = Some(0);
+        let res = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+        match res {
+            ContractError::InvalidApiKeyPolicy{} => {},
+            _ => panic!(),
         };
-        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_register_bot).unwrap();
-        assert_eq!(res, Response::new().add_attribute("action","register_bot")
-                                .add_attribute("hashed_api_key", hashed_api_key.clone())
-                                .add_attribute("moniker", moniker.clone())
-                                .add_attribute("bot_address", BOT));
-    
+    }
 
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(BOT) }).unwrap();
-        assert_eq!(res, to_binary(&Some(BotInfoQuery{
-            address: String::from(BOT),
-            hashed_api_key,
-            moniker,
-            last_update: Timestamp::from_seconds(100),
-        })).unwrap());
+    #[test]
+    fn migrate_from_v0_1_0_fail_with_callback_gas_limit_exceeded() {
+        let mut deps = v0_1_0_setup();
+
+        let mut msg = migrate_msg();
+        msg.max_callback_gas = Some(CALLBACK_LIMIT_GAS - 1);
+        let res = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+        match res {
+            ContractError::CallbackGasLimitExceeded{} => {},
+            _ => panic!(),
+        };
     }
 
     #[test]
-    fn get_bot_info_success_with_none() { 
+    fn migrate_success_with_same_version() {
         let mut deps = default_setup();
 
-        let hashed_api_key = String::from("elv0PecZXTaHIbs+3PvmJIz2BO9mtakvSpznFkhgfe/EtmPPCVAqpBDIT6ZeQ3TEsCvdxymXnDuSPKoqjlxZ/Q==");
-        let moniker = String::from("test bot");
+        let res = migrate(deps.as_mut(), mock_env(), migrate_msg()).unwrap();
+        assert_eq!(res, Response::new().add_attribute("action", "migrate")
+                                .add_attribute("from_version", env!("CARGO_PKG_VERSION"))
+                                .add_attribute("to_version", env!("CARGO_PKG_VERSION")));
+    }
 
-        let env: Env = Env {
-            block: BlockInfo {
-                height: 0,
-                time: Timestamp::from_seconds(100),
-                chain_id: "euphoria-2".to_string()
-            },
-            contract: ContractInfo {
-                address: Addr::unchecked(CONTRACT_ADDR)
-            },
-            transaction: None,
-        };
-        let request_register_bot = ExecuteMsg::RegisterBot {
-            hashed_api_key: hashed_api_key.clone(),
-            moniker: moniker.clone(),
+    #[test]
+    fn migrate_fail_with_downgrade() {
+        let mut deps = default_setup();
+        set_contract_version(deps.as_mut().storage, "crates.io:aurand", "99.0.0").unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), migrate_msg()).unwrap_err();
+        match res {
+            ContractError::InvalidContractVersion{} => {},
+            _ => panic!(),
         };
-        let res = execute(deps.as_mut(), env, mock_info(BOT, &[]), request_register_bot).unwrap();
-        assert_eq!(res, Response::new().add_attribute("action","register_bot")
-                                .add_attribute("hashed_api_key", hashed_api_key.clone())
-                                .add_attribute("moniker", moniker.clone())
-                                .add_attribute("bot_address", BOT));
-    
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBotInfo{ address: String::from(USER) }).unwrap();
-        assert_eq!(res, to_binary(&None::<BotInfoQuery>).unwrap());
     }
 
-    // REPLY
     #[test]
-    fn reply_success(){
+    fn migrate_fail_with_invalid_contract_name() {
         let mut deps = default_setup();
-        let res = reply(deps.as_mut(), mock_env(), cosmwasm_std::Reply { id: 1, result: SubMsgResult::Err(String::from(""))}).unwrap();
-        assert_eq!(res, Response::new());
+        set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.1.0").unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), migrate_msg()).unwrap_err();
+        match res {
+            ContractError::InvalidContractName{} => {},
+            _ => panic!(),
+        };
     }
-}
\ No newline at end of file
+}
@@ -27,18 +27,129 @@ pub enum ContractError {
     #[error("UnregisteredAddress")]
     UnregisteredAddress{},
 
+    #[error("BotDeregistered")]
+    BotDeregistered{},
+
+    #[error("ApiKeyDeregistered")]
+    ApiKeyDeregistered{},
+
+    #[error("ApiKeyInUse")]
+    ApiKeyInUse{},
+
     #[error("ToManyAction")]
     ToManyAction{},
 
     #[error("RSAVerificationFail")]
     RSAVerificationFail{},
 
+    #[error("InvalidVerificationKey")]
+    InvalidVerificationKey{},
+
+    #[error("VerificationKeyNotFound")]
+    VerificationKeyNotFound{},
+
     #[error("Uint128Overflow")]
     Uint128Overflow{},
 
+    #[error("InvalidRandomOrgParameter: {name}")]
+    InvalidRandomOrgParameter{name: String},
+
+    #[error("DeveloperLicenseRejected")]
+    DeveloperLicenseRejected{},
+
     #[error("InvalidApiKey")]
     InvalidApiKey{},
 
+    #[error("ApiKeyAlreadyAdded")]
+    ApiKeyAlreadyAdded{},
+
+    #[error("ApiKeyNotFound")]
+    ApiKeyNotFound{},
+
+    #[error("TooManyApiKeys")]
+    TooManyApiKeys{},
+
+    #[error("LastApiKey")]
+    LastApiKey{},
+
+    #[error("InvalidApiKeyPolicy")]
+    InvalidApiKeyPolicy{},
+
+    #[error("InvalidSerialNumber")]
+    InvalidSerialNumber{},
+
+    #[error("InvalidCompletionTime")]
+    InvalidCompletionTime{},
+
+    #[error("CommitmentNotFound")]
+    CommitmentNotFound{},
+
+    #[error("CommitmentNotExpired")]
+    CommitmentNotExpired{},
+
+    #[error("CallbackNotFailed")]
+    CallbackNotFailed{},
+
+    #[error("RandomnessNotFound")]
+    RandomnessNotFound{},
+
+    #[error("CallbackGasLimitExceeded")]
+    CallbackGasLimitExceeded{},
+
+    #[error("NoPendingOwner")]
+    NoPendingOwner{},
+
+    #[error("OwnershipProposalExpired")]
+    OwnershipProposalExpired{},
+
+    #[error("InvalidFeeSplit")]
+    InvalidFeeSplit{},
+
+    #[error("BotNotActive")]
+    BotNotActive{},
+
+    #[error("BotNotPending")]
+    BotNotPending{},
+
+    #[error("InsufficientStake")]
+    InsufficientStake{},
+
+    #[error("NoUnbondedStake")]
+    NoUnbondedStake{},
+
+    #[error("NoRewards")]
+    NoRewards{},
+
+    #[error("InsufficientTreasury")]
+    InsufficientTreasury{},
+
+    #[error("TreasuryNotEmpty")]
+    TreasuryNotEmpty{},
+
+    #[error("ConfigChangeNotFound")]
+    ConfigChangeNotFound{},
+
+    #[error("ConfigChangeNotDue")]
+    ConfigChangeNotDue{},
+
+    #[error("ConfigChangeExpired")]
+    ConfigChangeExpired{},
+
+    #[error("Paused: {operation}")]
+    Paused{operation: String},
+
+    #[error("RoleNotGranted")]
+    RoleNotGranted{},
+
+    #[error("InvalidContractName")]
+    InvalidContractName{},
+
+    #[error("InvalidContractVersion")]
+    InvalidContractVersion{},
+
+    #[error("UnexpectedDenom: {denom}")]
+    UnexpectedDenom{denom: String},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
 }
\ No newline at end of file
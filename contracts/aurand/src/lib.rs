@@ -5,5 +5,6 @@ pub mod state;
 mod rsa_verify;
 mod contract_test;
 mod utils;
+pub mod migrations;
 
 pub use crate::error::ContractError;
@@ -0,0 +1,199 @@
+use cosmwasm_std::{Storage, Uint128, Decimal, Order, StdResult, HexBinary, Timestamp};
+
+use crate::contract::{MAX_FEE_BPS, assert_callback_gas};
+use crate::error::ContractError;
+use crate::msg::MigrateMsg;
+use crate::state::{
+    CONFIGS, Configs, GAS_CONFIGS, GasConfigs, STAKING_CONFIGS, StakingConfigs,
+    COMMITMENTS, PENDING_COMMITMENTS, COMMITMENT_RECORDS, REQUEST_COMMITMENTS, COMMITMENT_STATUS,
+    Commitment, CommitmentStatus, DeliveryMode, TREASURY, Treasury,
+    REJECT_DEVELOPER_LICENSE, TIME_CONFIGS, API_KEY_POLICY, ApiKeyPolicy, BOTS, Bot, ApiKey, BotStatus, API_KEY_OWNERS, BOTS_BY_STATUS, add_verification_key,
+};
+use crate::rsa_verify::{RANDOM_ORG_MODULUS, RANDOM_ORG_EXPONENT};
+
+const DEFAULT_MAX_API_KEYS: u32 = 3;
+
+// state layout of version 0.1.0, only used for migrating old instances
+pub mod v0_1_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Timestamp, Uint128};
+    use cw_storage_plus::{Item, Map, Deque};
+
+    use crate::state::DataRequest;
+
+    #[cw_serde]
+    pub struct Commitment {
+        pub id: String,
+        pub request_id: String,
+        pub owner: Addr,
+        pub commit_time: Timestamp,
+        pub expired_time: Timestamp,
+        pub data_request: DataRequest,
+    }
+
+    pub const COMMITMENTS: Deque<Commitment> = Deque::new("commitments");
+    pub const PENDING_COMMITMENTS: Map<String, Commitment> = Map::new("pending commitments");
+
+    #[cw_serde]
+    pub struct Configs {
+        pub bounty_denom: String,
+        pub fee: Uint128,
+        pub callback_limit_gas: u64,
+        pub max_callback: u32,
+    }
+
+    pub const CONFIGS: Item<Configs> = Item::new("configs");
+
+    #[cw_serde]
+    pub struct Bot {
+        pub address: Addr,
+        pub hashed_api_key: String,
+        pub moniker: String,
+        pub last_update: Timestamp,
+    }
+
+    pub const BOTS: Map<Addr, Bot> = Map::new("bots");
+}
+
+// upgrade state of version 0.1.0 to current layout
+//  - configs get keeper reward, gas configs and staking configs
+//  - pending commitments get fee and delivery settings, and are backfilled to records, request index and status
+//  - commitments which are not pending anymore are dropped from queue
+//  - treasury reserves fees of pending commitments, assuming they were paid with current fee
+//  - bots keep their api key as the only key in use
+//  - random org key compiled into 0.1.0 becomes the first verification key
+//  - payloads of developer license keep being accepted unless owner sets otherwise
+pub fn migrate_from_v0_1_0(storage: &mut dyn Storage, msg: &MigrateMsg) -> Result<u32, ContractError> {
+    let legacy_configs = v0_1_0::CONFIGS.load(storage)?;
+
+    let protocol_fee_bps = msg.protocol_fee_bps.unwrap_or(0);
+    if protocol_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFeeSplit{});
+    }
+
+    CONFIGS.save(storage, &Configs {
+        bounty_denom: legacy_configs.bounty_denom,
+        fee: legacy_configs.fee,
+        callback_limit_gas: legacy_configs.callback_limit_gas,
+        max_callback: legacy_configs.max_callback,
+        keeper_reward: msg.keeper_reward.unwrap_or_else(|| Uint128::from(0u128)),
+        protocol_fee_bps,
+    })?;
+
+    let max_callback_gas = msg.max_callback_gas.unwrap_or(legacy_configs.callback_limit_gas);
+    assert_callback_gas(legacy_configs.callback_limit_gas, max_callback_gas)?;
+
+    GAS_CONFIGS.save(storage, &GasConfigs {
+        max_callback_gas,
+        callback_gas_price: msg.callback_gas_price.unwrap_or_else(Decimal::zero),
+    })?;
+
+    // registered bots have not bonded any stake, they keep working until min stake is raised
+    STAKING_CONFIGS.save(storage, &StakingConfigs {
+        min_stake: msg.min_stake.unwrap_or_else(Uint128::zero),
+        unbonding_period: msg.unbonding_period.unwrap_or(0),
+    })?;
+
+    // old cooldown of bot update was hard-coded as commitment lifetime plus one block
+    let time_configs = TIME_CONFIGS.load(storage)?;
+    let legacy_cooldown = time_configs.time_per_block + time_configs.time_expired;
+    let max_api_keys = msg.max_api_keys.unwrap_or(DEFAULT_MAX_API_KEYS);
+
+    // bot must be able to keep its api key
+    if max_api_keys == 0 {
+        return Err(ContractError::InvalidApiKeyPolicy{});
+    }
+
+    API_KEY_POLICY.save(storage, &ApiKeyPolicy {
+        update_cooldown: msg.api_key_update_cooldown.unwrap_or(legacy_cooldown),
+        grace_period: msg.api_key_grace_period.unwrap_or(legacy_cooldown),
+        max_api_keys,
+    })?;
+
+    let legacy_bots: Vec<v0_1_0::Bot> = v0_1_0::BOTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, bot)| bot))
+        .collect::<StdResult<_>>()?;
+    for legacy in legacy_bots {
+        API_KEY_OWNERS.save(storage, legacy.hashed_api_key.clone(), &legacy.address)?;
+        BOTS_BY_STATUS.save(storage, (BotStatus::Active.as_str().to_string(), legacy.address.clone()), &true)?;
+        BOTS.save(storage, legacy.address.clone(), &Bot {
+            address: legacy.address,
+            api_keys: vec![ApiKey {
+                hashed_api_key: legacy.hashed_api_key,
+                added_time: legacy.last_update,
+                valid_until: None,
+            }],
+            moniker: legacy.moniker,
+            last_update: legacy.last_update,
+            status: BotStatus::Active,
+        })?;
+    }
+
+    REJECT_DEVELOPER_LICENSE.save(storage, &msg.reject_developer_license.unwrap_or(false))?;
+
+    add_verification_key(
+        storage,
+        HexBinary::from_hex(RANDOM_ORG_MODULUS)?,
+        HexBinary::from_hex(RANDOM_ORG_EXPONENT)?,
+        Timestamp::from_seconds(0),
+        None,
+    )?;
+
+    // 0.1.0 does not record fee paid by each commitment, so fee at migration time is assumed for all of them.
+    // if fee was changed while a commitment was pending, its refund and reserved amount follow the current fee, not what was paid.
+    // old commitments are always delivered by callback to their owner
+    let upgrade = |legacy: v0_1_0::Commitment| Commitment {
+        id: legacy.id,
+        request_id: legacy.request_id,
+        owner: legacy.owner.clone(),
+        commit_time: legacy.commit_time,
+        expired_time: legacy.expired_time,
+        data_request: legacy.data_request,
+        fee: legacy_configs.fee,
+        delivery_mode: DeliveryMode::Callback,
+        callback_address: legacy.owner,
+        callback_payload: None,
+        callback_gas_limit: legacy_configs.callback_limit_gas,
+    };
+
+    // backfill pending commitments, oldest first so request index keeps the latest one
+    let mut pending: Vec<v0_1_0::Commitment> = v0_1_0::PENDING_COMMITMENTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, commitment)| commitment))
+        .collect::<StdResult<_>>()?;
+    pending.sort_by_key(|commitment| commitment.commit_time);
+
+    let count = pending.len() as u32;
+    let mut reserved = Uint128::zero();
+    for legacy in pending {
+        let commitment = upgrade(legacy);
+        reserved = reserved.checked_add(commitment.fee)
+            .map_err(|_| ContractError::Uint128Overflow{})?;
+        PENDING_COMMITMENTS.save(storage, commitment.id.clone(), &commitment)?;
+        COMMITMENT_RECORDS.save(storage, commitment.id.clone(), &commitment)?;
+        REQUEST_COMMITMENTS.save(storage, (commitment.owner.clone(), commitment.request_id.clone()), &commitment.id)?;
+        COMMITMENT_STATUS.save(storage, commitment.id.clone(), &CommitmentStatus::Pending)?;
+    }
+
+    // fees of migrated pending commitments are kept for refunds
+    TREASURY.save(storage, &Treasury {
+        protocol_fees: Uint128::zero(),
+        bot_rewards: Uint128::zero(),
+        reserved,
+        staked: Uint128::zero(),
+    })?;
+
+    // rebuild queue in the same order, newest commitment is at front
+    let queue: Vec<v0_1_0::Commitment> = v0_1_0::COMMITMENTS.iter(storage)?.collect::<StdResult<_>>()?;
+    while v0_1_0::COMMITMENTS.pop_back(storage)?.is_some() {}
+
+    for legacy in queue {
+        if !PENDING_COMMITMENTS.has(storage, legacy.id.clone()) {
+            continue;
+        }
+        COMMITMENTS.push_back(storage, &upgrade(legacy))?;
+    }
+
+    Ok(count)
+}
@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Uint128, Timestamp};
+use cosmwasm_std::{Uint128, Timestamp, Binary, Decimal, Coin, HexBinary};
 use nois::NoisCallback;
-use crate::state::Commitment;
+use crate::state::{Commitment, CommitmentStatus, FailedCallback, DeliveryMode, DerivedRandomness, Role, PauseStatus, ScheduledConfigChange, Unbonding, Slash, BotStatus, RegistrationMode, BotMetrics, ApiKey, BotTombstone, VerificationKey};
 
 /// Message type for `instantiate` entry_point
 #[cw_serde]
@@ -14,52 +14,229 @@ pub struct InstantiateMsg {
     pub nois_fee: Uint128,
     pub callback_limit_gas: u64,
     pub max_callback: u32,
+    pub keeper_reward: Uint128,
+    pub protocol_fee_bps: u16, // basis points, max 10000
+    pub max_callback_gas: u64,
+    pub callback_gas_price: Decimal,
+    pub config_change_delay: u64, // second
+    pub min_stake: Uint128, // stake that bot must bond when registering
+    pub unbonding_period: u64, // second
+    pub registration_mode: RegistrationMode, // open or allowlist bot registration
+    pub api_key_update_cooldown: u64, // second
+    pub api_key_grace_period: u64, // second
+    pub max_api_keys: u32, // max number of api keys of each bot
+    pub reject_developer_license: bool, // reject random org payloads of developer license, set on mainnet
+}
+
+/// Message type for `migrate` entry_point
+#[cw_serde]
+pub struct MigrateMsg {
+    pub keeper_reward: Option<Uint128>, // default is zero when migrating from 0.1.0
+    pub max_callback_gas: Option<u64>, // default is callback_limit_gas when migrating from 0.1.0
+    pub callback_gas_price: Option<Decimal>, // default is zero when migrating from 0.1.0
+    pub protocol_fee_bps: Option<u16>, // default is zero when migrating from 0.1.0, fulfiller takes the whole fee
+    pub min_stake: Option<Uint128>, // default is zero when migrating from 0.1.0, so registered bots keep working
+    pub unbonding_period: Option<u64>, // default is zero when migrating from 0.1.0
+    pub api_key_update_cooldown: Option<u64>, // default is time_per_block + time_expired when migrating from 0.1.0
+    pub api_key_grace_period: Option<u64>, // default is time_per_block + time_expired when migrating from 0.1.0
+    pub max_api_keys: Option<u32>, // default is 3 when migrating from 0.1.0
+    pub reject_developer_license: Option<bool>, // default is false when migrating from 0.1.0
 }
 
 /// Message type for `execute` entry_point
 #[cw_serde]
 pub enum ExecuteMsg {
-    // set contract configs
+    // schedule change of contract configs, it can be executed after config change delay
     SetConfigs {
-        bounty_denom: String, // denom string, ex: "ueaura"
+        bounty_denom: String, // denom string, ex: "ueaura", can only change while treasury is empty
         fee: Uint128, // fee of each random request
         callback_limit_gas: u64, // limmit gas of callback call for each request  
         max_callback: u32, // max number of callback submessage in each bot add randomness message
+        keeper_reward: Uint128, // reward taken from aurand fee for reclaiming someone else's expired commitment
+        protocol_fee_bps: u16, // share of aurand fee kept by treasury (basis points), the rest goes to bot or owner that fulfilled commitment
     },
 
-    // set nois configs
+    // schedule change of nois configs, it can be executed after config change delay
     SetNoisConfigs {
         nois_proxy: String, // addr of nois proxy contract on aura chain
         nois_fee: Uint128, // fee that nois proxy contract requires for each call
     },
 
+    // schedule change of limit and price of callback gas, it can be executed after config change delay
+    SetGasConfigs {
+        max_callback_gas: u64, // max callback gas limit that user can request
+        callback_gas_price: Decimal, // price of each callback gas unit, charged on top of fee
+    },
+
+    // schedule change of bot staking conditions, it can be executed after config change delay
+    SetStakingConfigs {
+        min_stake: Uint128, // stake that bot must bond to register and add randomness
+        unbonding_period: u64, // time before unbonded stake can be withdrawn (seconds)
+    },
+
+    // schedule change of bot api key policy, it can be executed after config change delay
+    SetApiKeyPolicy {
+        update_cooldown: u64, // min time between two api key changes of a bot (seconds)
+        grace_period: u64, // time a retired api key is still accepted (seconds)
+        max_api_keys: u32, // max number of api keys of a bot, retired keys in grace period included
+    },
+
+    // owner schedules change of config change delay, it can be executed after current delay
+    SetConfigChangeDelay {
+        delay: u64, // notice period of config changes (seconds)
+    },
+
+    // apply a scheduled config change whose delay has passed and that has not expired, anyone can call it
+    ExecuteConfigChange {
+        change_id: u64, // id of scheduled change
+    },
+
+    // drop a scheduled config change
+    CancelConfigChange {
+        change_id: u64, // id of scheduled change
+    },
+
     // set time conditions for commitments
     SetTimeConfigs {
         time_expired: u64, // lifetime of commitments (seconds), ex: 5s
         time_per_block: u64,  // time for block creation on aura chain (seconds), currently 5s/block
     },
 
-    // sign up bot for adding randomness and claiming reward
+    // owner proposes a new owner, who must accept it to take over the contract
+    ProposeOwner {
+        new_owner: String, // addr of new owner
+        expiry: Option<u64>, // lifetime of proposal (seconds), proposal never expires if not set
+    },
+
+    // pending owner accepts ownership
+    AcceptOwnership {},
+
+    // owner cancels pending ownership proposal
+    CancelOwnershipProposal {},
+
+    // owner gives up ownership, owner only messages are disabled forever
+    RenounceOwnership {},
+
+    // owner grants an admin role to address
+    GrantRole {
+        role: Role,
+        address: String, // addr of member
+    },
+
+    // owner revokes an admin role from address
+    RevokeRole {
+        role: Role,
+        address: String, // addr of member
+    },
+
+    // owner schedules a new random org public key, payloads completed inside its validity window are verified with it after config change delay
+    AddVerificationKey {
+        modulus: HexBinary, // big endian RSA modulus
+        exponent: HexBinary, // big endian RSA public exponent
+        valid_from: Timestamp, // start of validity window
+        valid_until: Option<Timestamp>, // end of validity window, key has no end if not set
+    },
+
+    // owner schedules change of end of validity window of a verification key, ex: when random org rotates its certificate
+    SetVerificationKeyExpiry {
+        id: u64, // id of verification key
+        valid_until: Option<Timestamp>, // key has no end if not set
+    },
+
+    // owner revokes a verification key, payloads signed with it are rejected
+    RevokeVerificationKey {
+        id: u64, // id of verification key
+    },
+
+    // owner sets whether random org payloads of developer license are rejected, ex: on mainnet
+    SetRejectDeveloperLicense {
+        reject: bool,
+    },
+
+    // pauser pauses or resumes operations, flags that are not set keep their current value
+    SetPauseStatus {
+        requests: Option<bool>, // new randomness requests
+        random_org: Option<bool>, // fulfillment by random org bots
+        nois: Option<bool>, // fulfillment by nois proxy
+        bot_registration: Option<bool>, // registration of new bots
+        expiry_extension: Option<u64>, // seconds added to lifetime of commitments created while a randomness source is paused
+    },
+
+    // sign up bot for adding randomness and claiming reward, bot must send at least min stake in bounty denom
     RegisterBot {
         hashed_api_key: String, // hash of random org api key
         moniker: String, // bot name
     },
     
-    // update bot information
+    // update bot information, api keys in use are replaced by new key and retired with grace period
     UpdateBot {
         hashed_api_key: String, // hash of random org api key
         moniker: String, // bot name
     },
 
-    // owner remove bot from contract
+    // bot adds an api key, payloads signed under any of its keys are accepted
+    AddApiKey {
+        hashed_api_key: String, // hash of random org api key
+    },
+
+    // bot retires an api key, it is still accepted until grace period is over
+    RetireApiKey {
+        hashed_api_key: String, // hash of random org api key
+    },
+
+    // bot leaves contract, its rewards are paid out and its bonded stake starts unbonding
+    // address and api keys can not register again until unbonding period is over
+    DeregisterBot {
+        recipient: Option<String>, // addr of rewards receiver, default is sender
+    },
+
+    // owner remove bot from contract, its bonded stake starts unbonding
     RemoveBot {
         address: String // addr of bot
     },
 
+    // bot manager switches between open and allowlist bot registration
+    SetRegistrationMode {
+        mode: RegistrationMode,
+    },
+
+    // bot manager approves a bot that is pending approval
+    ApproveBot {
+        address: String, // addr of bot
+    },
+
+    // bot manager rejects a bot that is pending approval, its stake can be withdrawn right away
+    RejectBot {
+        address: String, // addr of bot
+    },
+
+    // bot adds funds in bounty denom to its bonded stake
+    BondStake {},
+
+    // bot starts unbonding part of its stake, it can be withdrawn after unbonding period
+    UnbondStake {
+        amount: Uint128,
+    },
+
+    // bot withdraws stake whose unbonding period is over
+    WithdrawStake {},
+
+    // slash stake of a misbehaving bot, slashed stake is collected by treasury.
+    // stake is never slashed automatically, ex: randomness generated before a commitment only gets the payload rejected
+    SlashBot {
+        address: String, // addr of bot
+        amount: Uint128, // bonded stake is slashed first, then unbonding stake
+        reason: String,
+    },
+
     // user request for hex randomness
     RequestHexRandomness{
         request_id: String, // id of request
-        num: u32 // number of wanted randomness 
+        num: u32, // number of wanted randomness 
+        delivery_mode: Option<DeliveryMode>, // how randomness is delivered, default is callback
+        callback_address: Option<String>, // contract that receives callback, default is sender
+        callback_payload: Option<Binary>, // opaque data echoed back in callback
+        callback_gas_limit: Option<u64>, // gas limit of callback, default is callback_limit_gas, each gas unit is charged by callback_gas_price
     },
 
     // user request for integer randomness
@@ -68,6 +245,10 @@ pub enum ExecuteMsg {
         min: i32, // min value of each randomness
         max: i32, // max valud of each randomness
         num: u32, // number of wanted randomness
+        delivery_mode: Option<DeliveryMode>, // how randomness is delivered, default is callback
+        callback_address: Option<String>, // contract that receives callback, default is sender
+        callback_payload: Option<Binary>, // opaque data echoed back in callback
+        callback_gas_limit: Option<u64>, // gas limit of callback, default is callback_limit_gas, each gas unit is charged by callback_gas_price
     },
     
     // bot add randomness from random org
@@ -80,6 +261,33 @@ pub enum ExecuteMsg {
     NoisReceive {
         callback: NoisCallback // NoisCallback {job_id,randomness}
     },
+
+    // owner re-delivers randomness of a commitment whose callback failed
+    RetryCallback {
+        commitment_id: String, // id of commitment
+        gas_limit: u64, // gas limit of the new callback
+    },
+
+    // owner claims randomness of a pull mode commitment, randomness is returned in response data
+    ClaimRandomness {
+        commitment_id: String, // id of commitment
+    },
+
+    // bot claims its accrued rewards
+    ClaimRewards {
+        recipient: Option<String>, // addr of receiver, default is sender
+    },
+
+    // treasurer withdraws collected protocol fees
+    Withdraw {
+        amount: Uint128, // amount in bounty denom
+        recipient: String, // addr of receiver
+    },
+
+    // refund aurand fee of expired commitments to their owners, anyone can call it for a keeper reward
+    ReclaimExpired {
+        commitment_ids: Vec<String>, // ids of expired commitments
+    },
 }
 
 /// Message type for `query` entry_point
@@ -98,8 +306,58 @@ pub enum QueryMsg {
     #[returns(BotInfoQuery)]
     GetBotInfo{address: String}, // get bot information by address
 
+    #[returns(BotsQuery)]
+    ListBots{status: Option<BotStatus>, start_after: Option<String>, limit: Option<u32>}, // get registered bots, filtered by status if set
+
+    #[returns(Option<BotTombstone>)]
+    GetBotTombstone{address: String}, // get last record of a removed bot
+
+    #[returns(BotRewardsQuery)]
+    GetBotRewards{address: String}, // get claimable rewards of bot
+
     #[returns(ConfigsQuery)]
     GetConfigs{}, // get all contract configs
+
+    #[returns(OwnershipQuery)]
+    GetOwnership{}, // get current owner and pending ownership proposal
+
+    #[returns(ScheduledConfigChangesQuery)]
+    GetScheduledConfigChanges{start_after: Option<u64>, limit: Option<u32>}, // get config changes waiting to be executed
+
+    #[returns(VerificationKeysQuery)]
+    ListVerificationKeys{start_after: Option<u64>, limit: Option<u32>}, // get random org public keys, revoked ones included
+
+    #[returns(TreasuryQuery)]
+    GetTreasury{}, // get collected fees, owed bot rewards and reserved funds
+
+    #[returns(PauseStatus)]
+    GetPauseStatus{}, // get pause flags of operations
+
+    #[returns(bool)]
+    HasRole{role: Role, address: String}, // check if address can act as role, owner holds every role
+
+    #[returns(RoleMembersQuery)]
+    ListRoleMembers{role: Role, start_after: Option<String>, limit: Option<u32>}, // get addresses that have been granted role, owner holds every role implicitly and is not listed
+
+    #[returns(Option<CommitmentQuery>)]
+    GetCommitment{id: String}, // get commitment and its status by commitment id
+
+    #[returns(Option<CommitmentQuery>)]
+    GetCommitmentByRequest{owner: String, request_id: String}, // get latest commitment and its status by owner's request id
+
+    #[returns(Option<FailedCallback>)]
+    GetFailedCallback{commitment_id: String}, // get error and undelivered randomness of a failed callback
+
+    #[returns(Option<DerivedRandomness>)]
+    GetRandomnessResult{commitment_id: String}, // get unclaimed randomness of a pull mode commitment
+
+    #[returns(RequestPriceQuery)]
+    GetRequestPrice{
+        data_type: String, // "hex" or "int"
+        num: u32, // number of wanted randomness
+        delivery_mode: Option<DeliveryMode>, // default is callback
+        callback_gas_limit: Option<u64>, // default is callback_limit_gas
+    }, // get funds that a randomness request must send
 }
 
 #[cw_serde]
@@ -112,6 +370,20 @@ pub struct CommitmentsQuery {
     pub commitments: Vec<Commitment>
 }
 
+#[cw_serde]
+pub struct CommitmentQuery {
+    pub commitment: Commitment,
+    pub status: CommitmentStatus,
+}
+
+#[cw_serde]
+pub struct RequestPriceQuery {
+    pub funds: Vec<Coin>, // exact funds to attach to the request
+    pub fee: Uint128, // aurand fee, including callback gas
+    pub nois_fee: Uint128,
+    pub callback_gas_limit: u64,
+}
+
 #[cw_serde]
 pub struct NumberOfCommitmentQuery {
     pub num: u32
@@ -120,9 +392,59 @@ pub struct NumberOfCommitmentQuery {
 #[cw_serde]
 pub struct BotInfoQuery {
     pub address: String,
-    pub hashed_api_key: String, // hash of random-org api key
+    pub api_keys: Vec<ApiKey>, // keys in use and retired keys in grace period
     pub moniker: String,
     pub last_update: Timestamp,
+    pub status: BotStatus,
+    pub bonded: Uint128, // stake that backs bot
+    pub unbonding: Vec<Unbonding>,
+    pub slashes: Vec<Slash>,
+    pub metrics: BotMetrics, // performance counters
+}
+
+#[cw_serde]
+pub struct BotsQuery {
+    pub bots: Vec<BotInfoQuery>,
+}
+
+#[cw_serde]
+pub struct BotRewardsQuery {
+    pub address: String,
+    pub denom: String,
+    pub amount: Uint128, // claimable rewards
+}
+
+#[cw_serde]
+pub struct TreasuryQuery {
+    pub denom: String,
+    pub protocol_fees: Uint128, // withdrawable
+    pub bot_rewards: Uint128, // owed to bots
+    pub reserved: Uint128, // fees of pending commitments
+    pub staked: Uint128, // bonded and unbonding stake of bots
+    pub balance: Uint128, // contract balance of denom
+    pub unaccounted: Uint128, // balance that is not tracked by treasury
+}
+
+#[cw_serde]
+pub struct ScheduledConfigChangesQuery {
+    pub changes: Vec<ScheduledConfigChange>,
+}
+
+#[cw_serde]
+pub struct VerificationKeysQuery {
+    pub keys: Vec<VerificationKey>,
+}
+
+#[cw_serde]
+pub struct RoleMembersQuery {
+    pub members: Vec<String>,
+}
+
+#[cw_serde]
+pub struct OwnershipQuery {
+    pub owner: Option<String>, // none if ownership has been renounced
+    pub pending_owner: Option<String>,
+    pub pending_expires_at: Option<Timestamp>,
 }
 
 #[cw_serde]
@@ -134,6 +456,18 @@ pub struct ConfigsQuery {
     pub fee: Uint128,
     pub nois_fee: Uint128,
     pub callback_limit_gas: u64,
+    pub keeper_reward: Uint128,
+    pub protocol_fee_bps: u16,
+    pub max_callback_gas: u64,
+    pub callback_gas_price: Decimal,
+    pub config_change_delay: u64,
+    pub min_stake: Uint128,
+    pub unbonding_period: u64,
+    pub registration_mode: RegistrationMode,
+    pub api_key_update_cooldown: u64,
+    pub api_key_grace_period: u64,
+    pub max_api_keys: u32,
+    pub reject_developer_license: bool,
 }
 
 // callback function that user must define in contract for receiving aurand randomness
@@ -141,11 +475,22 @@ pub struct ConfigsQuery {
 pub enum CallbackExecuteMsg {
     ReceiveHexRandomness{
         request_id: String,
-        randomness: Vec<String>
+        randomness: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<Binary>, // callback payload of request, omitted if not set
     },
 
     ReceiveIntRandomness{
         request_id: String,
-        randomness: Vec<i32>
+        randomness: Vec<i32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<Binary>, // callback payload of request, omitted if not set
+    },
+
+    // optional, notify user that commitment has expired and aurand fee has been refunded
+    ReceiveRandomnessExpired{
+        request_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<Binary>, // callback payload of request, omitted if not set
     },
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr,Timestamp,Storage,StdResult,Uint128};
+use cosmwasm_std::{Addr,Timestamp,Storage,StdResult,Uint128,HexBinary,Binary,Decimal,Order};
 use cw_storage_plus::{Item, Map, Deque};
 
 #[cw_serde]
@@ -10,6 +10,12 @@ pub struct DataRequest {
     pub data_type: String,
 }
 
+#[cw_serde]
+pub enum DeliveryMode {
+    Callback, // randomness is pushed to owner by `CallbackExecuteMsg`
+    Pull, // randomness is kept in contract state until owner claims it
+}
+
 #[cw_serde]
 pub struct Commitment {
     pub id: String,
@@ -18,16 +24,104 @@ pub struct Commitment {
     pub commit_time: Timestamp,
     pub expired_time: Timestamp,
     pub data_request: DataRequest,
+    pub fee: Uint128, // aurand fee paid for this commitment, used for bounty and refund
+    pub delivery_mode: DeliveryMode,
+    pub callback_address: Addr, // receiver of callback, owner still pays fee and gets refund
+    pub callback_payload: Option<Binary>, // opaque data echoed back in callback
+    pub callback_gas_limit: u64, // gas limit of callback submessage, zero for pull mode
 }
 
 pub const COMMITMENTS: Deque<Commitment> = Deque::new("commitments"); // a list of commitments ordered by commit time that is used to determine which commits satisfy the time conditions
 pub const PENDING_COMMITMENTS: Map<String, Commitment> = Map::new("pending commitments"); // map of commitments, use for getting commitment's information
+pub const COMMITMENT_RECORDS: Map<String, Commitment> = Map::new("commitment records"); // every commitment ever made, kept after it has been fulfilled or refunded
+pub const REQUEST_COMMITMENTS: Map<(Addr, String), String> = Map::new("request commitments"); // (owner, request_id) => id of the latest commitment made for that request
+
+#[cw_serde]
+pub enum RandomnessSource {
+    RandomOrg,
+    Nois,
+}
+
+#[cw_serde]
+pub enum CommitmentStatus {
+    Pending,
+    Fulfilled {
+        source: RandomnessSource,
+        time: Timestamp,
+    },
+    // never stored, reported by queries for pending commitment that has passed `expired_time` and can be reclaimed
+    Expired,
+    Refunded {
+        time: Timestamp,
+    },
+    // randomness has been derived but user callback failed
+    CallbackFailed {
+        source: RandomnessSource,
+        time: Timestamp,
+    },
+}
+
+pub const COMMITMENT_STATUS: Map<String, CommitmentStatus> = Map::new("commitment status"); // delivery state of each commitment, shared by random org and nois fulfillment
+
+// add new commitment to queue and mark it as pending
+pub fn add_commitment(
+    storage: &mut dyn Storage,
+    commitment: &Commitment,
+) -> StdResult<()> {
+    COMMITMENTS.push_front(storage, commitment)?;
+    PENDING_COMMITMENTS.save(storage, commitment.id.clone(), commitment)?;
+    COMMITMENT_RECORDS.save(storage, commitment.id.clone(), commitment)?;
+    REQUEST_COMMITMENTS.save(storage, (commitment.owner.clone(), commitment.request_id.clone()), &commitment.id)?;
+    COMMITMENT_STATUS.save(storage, commitment.id.clone(), &CommitmentStatus::Pending)?;
+
+    // fee of pending commitment is kept for a possible refund
+    let mut treasury = TREASURY.may_load(storage)?.unwrap_or_default();
+    treasury.reserved = treasury.reserved.checked_add(commitment.fee)?;
+    TREASURY.save(storage, &treasury)?;
+    Ok(())
+}
+
+// move pending commitment to its final status, return false if commitment is not pending anymore
+// every fulfillment and refund path must go through this function so a commitment is only settled once
+pub fn settle_commitment(
+    storage: &mut dyn Storage,
+    commit_id: String,
+    status: CommitmentStatus,
+) -> StdResult<bool> {
+    let current_status = COMMITMENT_STATUS.may_load(storage, commit_id.clone())?;
+    if current_status != Some(CommitmentStatus::Pending) {
+        return Ok(false);
+    }
+
+    // release reserved fee, caller pays it out or credits it to treasury
+    if let Some(commitment) = PENDING_COMMITMENTS.may_load(storage, commit_id.clone())? {
+        let mut treasury = TREASURY.may_load(storage)?.unwrap_or_default();
+        treasury.reserved = treasury.reserved.checked_sub(commitment.fee)?;
+        TREASURY.save(storage, &treasury)?;
+    }
+
+    COMMITMENT_STATUS.save(storage, commit_id.clone(), &status)?;
+    PENDING_COMMITMENTS.remove(storage, commit_id);
+
+    Ok(true)
+}
+
+// mark commitment as fulfilled by `source`, return false if commitment is not pending anymore
+pub fn fulfill_commitment(
+    storage: &mut dyn Storage,
+    commit_id: String,
+    source: RandomnessSource,
+    time: Timestamp,
+) -> StdResult<bool> {
+    settle_commitment(storage, commit_id, CommitmentStatus::Fulfilled { source, time })
+}
 
 // get commitments that meet time conditions 
 //      commit_time <= completion_time <= expired_time
 pub fn get_commitments(
     storage: &mut dyn Storage,
     completion_time: Timestamp,
+    fulfill_time: Timestamp,
     max_callback: u32,
 ) -> StdResult<Vec<Commitment>> {
     let mut count: u32 = 0;
@@ -36,11 +130,10 @@ pub fn get_commitments(
         // pop commitment from queue
         let commitment = COMMITMENTS.pop_back(storage)?;
 
-        if commitment.is_none(){
-            break;
-        }
-
-        let commitment = commitment.unwrap();
+        let commitment = match commitment {
+            Some(commitment) => commitment,
+            None => break,
+        };
 
         // if meet a commitment has `commit_time` greater than `completion_time`, push it back to queue and return current list
         if commitment.commit_time.ge(&completion_time) {
@@ -53,9 +146,12 @@ pub fn get_commitments(
             continue;
         }
 
-        vecs.push(commitment.clone());
+        // if commitment has been fulfilled by nois, drop it from queue
+        if !fulfill_commitment(storage, commitment.id.clone(), RandomnessSource::RandomOrg, fulfill_time)? {
+            continue;
+        }
 
-        PENDING_COMMITMENTS.remove(storage, commitment.id);
+        vecs.push(commitment);
 
         count += 1;
         if count == max_callback {
@@ -63,35 +159,193 @@ pub fn get_commitments(
         }
     }
 
-    return Ok(vecs);
+    Ok(vecs)
+}
+
+// get the oldest commitment in queue that is still waiting for randomness
+// settled commitments (fulfilled by nois, refunded) met on the way are popped from queue, so each one is only visited once
+pub fn get_oldest_pending_commitment(
+    storage: &mut dyn Storage,
+) -> StdResult<Option<Commitment>> {
+    while let Some(commitment) = COMMITMENTS.back(storage)? {
+        if COMMITMENT_STATUS.may_load(storage, commitment.id.clone())? == Some(CommitmentStatus::Pending) {
+            return Ok(Some(commitment));
+        }
+        COMMITMENTS.pop_back(storage)?;
+    }
+
+    Ok(None)
 }
 
-// get commitment from PENDING_COMMIMENTS by id
+// get pending commitment by id and mark it as fulfilled by nois
 pub fn get_commitment(
     storage: &mut dyn Storage,
     commit_id: String,
+    fulfill_time: Timestamp,
 ) -> StdResult<Option<Commitment>> {
+    let commitment = PENDING_COMMITMENTS.may_load(storage, commit_id.clone())?;
 
-    if PENDING_COMMITMENTS.has(storage, commit_id.clone()) {
-        let commitment = PENDING_COMMITMENTS.load(storage, commit_id.clone())?;
-        PENDING_COMMITMENTS.remove(storage, commit_id);
+    if commitment.is_some() && fulfill_commitment(storage, commit_id, RandomnessSource::Nois, fulfill_time)? {
+        return Ok(commitment);
+    }
+
+    Ok(None)
+}
 
-        return Ok(Some(commitment));
+
+#[cw_serde]
+pub enum DerivedRandomness {
+    Hex(Vec<String>),
+    Int(Vec<i32>),
+}
+
+#[cw_serde]
+pub struct CallbackDelivery {
+    pub commitment_id: String,
+    pub randomness: HexBinary, // seed that the callback randomness is derived from
+}
+
+pub const CALLBACK_DELIVERIES: Map<u64, CallbackDelivery> = Map::new("callback delivery replies"); // callbacks waiting for their reply, keyed by reply id of their submessage
+pub const CALLBACK_DELIVERY_COUNT: Item<u64> = Item::new("callback delivery count"); // number of callbacks ever sent, gives each callback its own reply id
+
+#[cw_serde]
+pub struct FailedCallback {
+    pub error: String,
+    pub randomness: DerivedRandomness, // randomness that could not be delivered
+    pub seed: HexBinary,
+    pub time: Timestamp,
+}
+
+pub const FAILED_CALLBACKS: Map<String, FailedCallback> = Map::new("failed callbacks");
+pub const RANDOMNESS_RESULTS: Map<String, DerivedRandomness> = Map::new("randomness results"); // randomness of pull mode commitments waiting to be claimed
+
+#[cw_serde]
+#[derive(Default)]
+pub enum BotStatus {
+    PendingApproval, // registered in allowlist mode, waiting for bot manager
+    #[default]
+    Active, // can add randomness
+}
+
+impl BotStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BotStatus::PendingApproval => "pending_approval",
+            BotStatus::Active => "active",
+        }
     }
+}
 
-    return Ok(None);
+#[cw_serde]
+pub struct ApiKey {
+    pub hashed_api_key: String, // hash of random-org api key
+    pub added_time: Timestamp,
+    pub valid_until: Option<Timestamp>, // retired key is still accepted until this time, none if key is in use
 }
 
+impl ApiKey {
+    // key can sign payloads submitted at `time`
+    pub fn is_valid(&self, time: Timestamp) -> bool {
+        self.valid_until.is_none_or(|valid_until| time.lt(&valid_until))
+    }
+}
 
 #[cw_serde]
 pub struct Bot {
     pub address: Addr,
-    pub hashed_api_key: String, // hash of random-org api key
+    pub api_keys: Vec<ApiKey>, // keys in use and retired keys in grace period
     pub moniker: String,
     pub last_update: Timestamp,
+    #[serde(default)]
+    pub status: BotStatus, // bots registered before approval workflow are active
 }
 
 pub const BOTS: Map<Addr, Bot> = Map::new("bots");
+pub const BOTS_BY_STATUS: Map<(String, Addr), bool> = Map::new("bots by status"); // (status, bot) => registered, kept in sync with status of bots so they can be listed by status
+pub const API_KEY_OWNERS: Map<String, Addr> = Map::new("api key owners"); // hashed api key => last bot that put it in use, stale once the key leaves that bot
+
+#[cw_serde]
+pub struct BotTombstone {
+    pub moniker: String,
+    pub hashed_api_keys: Vec<String>,
+    pub removed_time: Timestamp,
+    pub reregister_time: Timestamp, // address and api keys can not be registered again before this time
+}
+
+pub const BOT_TOMBSTONES: Map<Addr, BotTombstone> = Map::new("bot tombstones"); // last record of each removed bot
+pub const API_KEY_TOMBSTONES: Map<String, Timestamp> = Map::new("api key tombstones"); // hashed api key of removed bot => time it can be used again
+
+// keep record of removed bot, so it can not register again to dodge penalties before `reregister_time`
+pub fn tombstone_bot(
+    storage: &mut dyn Storage,
+    bot: &Bot,
+    removed_time: Timestamp,
+    reregister_time: Timestamp,
+) -> StdResult<()> {
+    let hashed_api_keys: Vec<String> = bot.api_keys.iter().map(|api_key| api_key.hashed_api_key.clone()).collect();
+    for hashed_api_key in hashed_api_keys.iter() {
+        API_KEY_TOMBSTONES.save(storage, hashed_api_key.clone(), &reregister_time)?;
+    }
+
+    BOT_TOMBSTONES.save(storage, bot.address.clone(), &BotTombstone {
+        moniker: bot.moniker.clone(),
+        hashed_api_keys,
+        removed_time,
+        reregister_time,
+    })
+}
+
+#[cw_serde]
+pub struct ApiKeyPolicy {
+    pub update_cooldown: u64, // min time between two api key changes of a bot (seconds)
+    pub grace_period: u64, // time a retired api key is still accepted (seconds)
+    pub max_api_keys: u32, // max number of keys of a bot, retired keys in grace period included
+}
+
+pub const API_KEY_POLICY: Item<ApiKeyPolicy> = Item::new("api key policy");
+
+#[cw_serde]
+#[derive(Default)]
+pub enum RegistrationMode {
+    #[default]
+    Open, // registered bots are active right away
+    Allowlist, // registered bots wait for approval of bot manager
+}
+
+pub const REGISTRATION_MODE: Item<RegistrationMode> = Item::new("registration mode");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct BotMetrics {
+    pub fulfilled_commitments: u64, // commitments delivered by bot
+    pub submitted_payloads: u64, // random org payloads accepted for processing, rejected payloads revert the transaction and are not counted
+    pub total_bounty: Uint128, // bounty earned by fulfillments, in bounty denom
+    pub last_fulfillment: Option<Timestamp>,
+}
+
+pub const BOT_METRICS: Map<Addr, BotMetrics> = Map::new("bot metrics"); // kept after bot is removed
+
+// update counters of bot after a payload submission
+pub fn record_submission(
+    storage: &mut dyn Storage,
+    bot: &Addr,
+    fulfilled: u64,
+    bounty: Uint128,
+    time: Timestamp,
+) -> StdResult<()> {
+    let mut metrics = BOT_METRICS.may_load(storage, bot.clone())?.unwrap_or_default();
+    metrics.submitted_payloads += 1;
+    if fulfilled > 0 {
+        metrics.fulfilled_commitments += fulfilled;
+        metrics.total_bounty = metrics.total_bounty.checked_add(bounty)?;
+        metrics.last_fulfillment = Some(time);
+    }
+    BOT_METRICS.save(storage, bot.clone(), &metrics)
+}
+
+pub const REJECT_DEVELOPER_LICENSE: Item<bool> = Item::new("reject developer license"); // set on mainnet, payloads of random org developer keys are accepted if not set
+pub const BOT_REWARDS: Map<Addr, Uint128> = Map::new("bot rewards"); // claimable rewards of each bot, in bounty denom
+pub const SERIAL_NUMBERS: Map<String, u32> = Map::new("serial numbers"); // last accepted random org serial number of each hashed api key
 
 #[cw_serde]
 pub struct NoisConfigs {
@@ -109,19 +363,343 @@ pub struct TimeConfigs {
 
 pub const TIME_CONFIGS: Item<TimeConfigs> = Item::new("time configs");
 
+#[cw_serde]
+pub struct GasConfigs {
+    pub max_callback_gas: u64, // max callback gas limit that user can request
+    pub callback_gas_price: Decimal, // price of each callback gas unit, in bounty denom
+}
+
+pub const GAS_CONFIGS: Item<GasConfigs> = Item::new("gas configs");
+
+#[cw_serde]
+pub struct StakingConfigs {
+    pub min_stake: Uint128, // stake that bot must bond to register and add randomness, in bounty denom
+    pub unbonding_period: u64, // second
+}
+
+pub const STAKING_CONFIGS: Item<StakingConfigs> = Item::new("staking configs");
+
 #[cw_serde]
 pub struct Configs {
     pub bounty_denom: String,
     pub fee: Uint128,
     pub callback_limit_gas: u64,
     pub max_callback: u32, 
+    pub keeper_reward: Uint128, // reward for reclaiming an expired commitment on behalf of its owner
+    pub protocol_fee_bps: u16, // share of aurand fee kept by treasury (basis points), the rest goes to fulfiller
 }
 
 pub const CONFIGS: Item<Configs> = Item::new("configs");
 
-pub const OWNER: Item<Addr> = Item::new("owner");
+#[cw_serde]
+pub enum ConfigChange {
+    Configs(Configs),
+    NoisConfigs(NoisConfigs),
+    GasConfigs(GasConfigs),
+    StakingConfigs(StakingConfigs),
+    ApiKeyPolicy(ApiKeyPolicy),
+    Delay(u64), // new config change delay (seconds)
+    AddVerificationKey {
+        modulus: HexBinary,
+        exponent: HexBinary,
+        valid_from: Timestamp,
+        valid_until: Option<Timestamp>,
+    },
+    VerificationKeyExpiry {
+        id: u64, // id of verification key
+        valid_until: Option<Timestamp>,
+    },
+}
+
+impl ConfigChange {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ConfigChange::Configs(_) => "configs",
+            ConfigChange::NoisConfigs(_) => "nois_configs",
+            ConfigChange::GasConfigs(_) => "gas_configs",
+            ConfigChange::StakingConfigs(_) => "staking_configs",
+            ConfigChange::ApiKeyPolicy(_) => "api_key_policy",
+            ConfigChange::Delay(_) => "config_change_delay",
+            ConfigChange::AddVerificationKey { .. } => "add_verification_key",
+            ConfigChange::VerificationKeyExpiry { .. } => "verification_key_expiry",
+        }
+    }
+
+    // queued changes with the same target replace each other
+    pub fn target(&self) -> String {
+        match self {
+            ConfigChange::AddVerificationKey { modulus, exponent, .. } => format!("{}:{}:{}", self.kind(), modulus.to_hex(), exponent.to_hex()),
+            ConfigChange::VerificationKeyExpiry { id, .. } => format!("{}:{}", self.kind(), id),
+            _ => self.kind().to_string(),
+        }
+    }
+
+    // role that can schedule and cancel this change, none means owner only
+    pub fn role(&self) -> Option<Role> {
+        match self {
+            ConfigChange::Configs(_) | ConfigChange::GasConfigs(_) | ConfigChange::StakingConfigs(_) | ConfigChange::ApiKeyPolicy(_) => Some(Role::ConfigAdmin),
+            ConfigChange::NoisConfigs(_) => Some(Role::NoisAdmin),
+            ConfigChange::Delay(_) | ConfigChange::AddVerificationKey { .. } | ConfigChange::VerificationKeyExpiry { .. } => None,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct ScheduledConfigChange {
+    pub id: u64,
+    pub change: ConfigChange,
+    pub proposer: Addr,
+    pub scheduled_time: Timestamp,
+    pub effective_time: Timestamp, // change can be executed from this time
+    pub expired_time: Timestamp, // change can not be executed after this time
+}
+
+pub const SCHEDULED_CONFIG_CHANGES: Map<u64, ScheduledConfigChange> = Map::new("scheduled config changes");
+pub const CONFIG_CHANGE_COUNT: Item<u64> = Item::new("config change count"); // id of last scheduled config change
+pub const CONFIG_CHANGE_DELAY: Item<u64> = Item::new("config change delay"); // notice period of config changes (seconds), zero if not set
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Treasury {
+    pub protocol_fees: Uint128, // collected fees that can be withdrawn
+    pub bot_rewards: Uint128, // bounties earned by bots and not yet paid out
+    pub reserved: Uint128, // fees of pending commitments, kept for refunds
+    pub staked: Uint128, // bonded and unbonding stake of bots
+}
+
+impl Treasury {
+    pub fn is_empty(&self) -> bool {
+        self.protocol_fees.is_zero() && self.bot_rewards.is_zero() && self.reserved.is_zero() && self.staked.is_zero()
+    }
+}
+
+pub const TREASURY: Item<Treasury> = Item::new("treasury"); // amounts in bounty denom
+
+// add fee to withdrawable protocol fees
+pub fn credit_protocol_fees(
+    storage: &mut dyn Storage,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut treasury = TREASURY.may_load(storage)?.unwrap_or_default();
+    treasury.protocol_fees = treasury.protocol_fees.checked_add(amount)?;
+    TREASURY.save(storage, &treasury)
+}
+
+// accrue bounty to bot, it is paid out when bot claims rewards
+pub fn credit_bot_rewards(
+    storage: &mut dyn Storage,
+    bot: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let rewards = BOT_REWARDS.may_load(storage, bot.clone())?.unwrap_or_default();
+    BOT_REWARDS.save(storage, bot.clone(), &rewards.checked_add(amount)?)?;
+
+    let mut treasury = TREASURY.may_load(storage)?.unwrap_or_default();
+    treasury.bot_rewards = treasury.bot_rewards.checked_add(amount)?;
+    TREASURY.save(storage, &treasury)
+}
+
+// take all accrued rewards of bot out of treasury, caller pays them out
+pub fn take_bot_rewards(
+    storage: &mut dyn Storage,
+    bot: &Addr,
+) -> StdResult<Uint128> {
+    let rewards = BOT_REWARDS.may_load(storage, bot.clone())?.unwrap_or_default();
+    if rewards.is_zero() {
+        return Ok(rewards);
+    }
+    BOT_REWARDS.remove(storage, bot.clone());
+
+    let mut treasury = TREASURY.may_load(storage)?.unwrap_or_default();
+    treasury.bot_rewards = treasury.bot_rewards.checked_sub(rewards)?;
+    TREASURY.save(storage, &treasury)?;
+
+    Ok(rewards)
+}
+
+#[cw_serde]
+pub struct Unbonding {
+    pub amount: Uint128,
+    pub release_time: Timestamp, // stake can be withdrawn after this time
+}
+
+#[cw_serde]
+pub struct Slash {
+    pub amount: Uint128,
+    pub reason: String,
+    pub time: Timestamp,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct BotStake {
+    pub bonded: Uint128,
+    pub unbonding: Vec<Unbonding>, // stake is still slashable while unbonding
+    pub slashes: Vec<Slash>, // slashing history
+}
+
+pub const BOT_STAKES: Map<Addr, BotStake> = Map::new("bot stakes");
+
+// slash bonded stake first, then unbonding stake from the latest one, slashed stake is collected by treasury
+pub fn slash_stake(
+    storage: &mut dyn Storage,
+    bot: &Addr,
+    amount: Uint128,
+    reason: String,
+    time: Timestamp,
+) -> StdResult<Uint128> {
+    let mut stake = BOT_STAKES.may_load(storage, bot.clone())?.unwrap_or_default();
+
+    let mut remaining = amount;
+    let from_bonded = remaining.min(stake.bonded);
+    stake.bonded -= from_bonded;
+    remaining -= from_bonded;
+
+    for unbonding in stake.unbonding.iter_mut().rev() {
+        let from_unbonding = remaining.min(unbonding.amount);
+        unbonding.amount -= from_unbonding;
+        remaining -= from_unbonding;
+    }
+    stake.unbonding.retain(|unbonding| !unbonding.amount.is_zero());
+
+    let slashed = amount - remaining;
+    if slashed.is_zero() {
+        return Ok(slashed);
+    }
+
+    stake.slashes.push(Slash {
+        amount: slashed,
+        reason,
+        time,
+    });
+    BOT_STAKES.save(storage, bot.clone(), &stake)?;
+
+    let mut treasury = TREASURY.may_load(storage)?.unwrap_or_default();
+    treasury.staked = treasury.staked.checked_sub(slashed)?;
+    treasury.protocol_fees = treasury.protocol_fees.checked_add(slashed)?;
+    TREASURY.save(storage, &treasury)?;
+
+    Ok(slashed)
+}
+
+#[cw_serde]
+pub struct VerificationKey {
+    pub id: u64,
+    pub modulus: HexBinary, // big endian RSA modulus of random org signing certificate
+    pub exponent: HexBinary, // big endian RSA public exponent
+    pub valid_from: Timestamp, // payloads completed from this time are verified with key
+    pub valid_until: Option<Timestamp>, // payloads completed from this time are not verified with key, none if key has no end
+    pub revoked: bool, // revoked key verifies nothing
+}
+
+impl VerificationKey {
+    // key can verify payloads whose completion time is `time`
+    pub fn is_valid_at(&self, time: Timestamp) -> bool {
+        !self.revoked
+            && self.valid_from.le(&time)
+            && self.valid_until.is_none_or(|valid_until| time.lt(&valid_until))
+    }
+}
+
+pub const VERIFICATION_KEYS: Map<u64, VerificationKey> = Map::new("verification keys");
+pub const VERIFICATION_KEY_COUNT: Item<u64> = Item::new("verification key count"); // id of last added verification key
+
+// store new verification key and return its id
+pub fn add_verification_key(
+    storage: &mut dyn Storage,
+    modulus: HexBinary,
+    exponent: HexBinary,
+    valid_from: Timestamp,
+    valid_until: Option<Timestamp>,
+) -> StdResult<u64> {
+    let id = VERIFICATION_KEY_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+
+    VERIFICATION_KEY_COUNT.save(storage, &id)?;
+    VERIFICATION_KEYS.save(storage, id, &VerificationKey {
+        id,
+        modulus,
+        exponent,
+        valid_from,
+        valid_until,
+        revoked: false,
+    })?;
+
+    Ok(id)
+}
+
+// get keys that can verify payloads completed at `time`
+pub fn get_verification_keys_at(
+    storage: &dyn Storage,
+    time: Timestamp,
+) -> StdResult<Vec<VerificationKey>> {
+    VERIFICATION_KEYS
+        .range(storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, key)) => key.is_valid_at(time),
+            Err(_) => true,
+        })
+        .map(|item| item.map(|(_, key)| key))
+        .collect()
+}
+
+pub const OWNER: Item<Addr> = Item::new("owner"); // removed when ownership is renounced
+
+#[cw_serde]
+pub struct PendingOwner {
+    pub address: Addr,
+    pub expires_at: Option<Timestamp>, // proposal can not be accepted from this time
+}
+
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending owner");
 pub const NONCES: Map<Addr, u64> = Map::new("nonces");
 
+#[cw_serde]
+pub enum Role {
+    ConfigAdmin, // manage contract, time and gas configs
+    NoisAdmin, // manage nois proxy configs
+    BotManager, // manage bots
+    Treasurer, // withdraw contract funds
+    Pauser, // pause contract operations
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::ConfigAdmin => "config_admin",
+            Role::NoisAdmin => "nois_admin",
+            Role::BotManager => "bot_manager",
+            Role::Treasurer => "treasurer",
+            Role::Pauser => "pauser",
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseStatus {
+    pub requests: bool, // new randomness requests
+    pub random_org: bool, // fulfillment by `AddRandomness`
+    pub nois: bool, // fulfillment by `NoisReceive`
+    pub bot_registration: bool,
+    pub expiry_extension: u64, // seconds added to lifetime of commitments created while a randomness source is paused
+}
+
+pub const PAUSE_STATUS: Item<PauseStatus> = Item::new("pause status"); // nothing is paused if not set
+
+pub const ROLES: Map<(String, Addr), bool> = Map::new("roles"); // (role, member) => granted
+
+// owner holds every role, other addresses must be granted by owner
+pub fn has_role(
+    storage: &dyn Storage,
+    role: &Role,
+    address: &Addr,
+) -> StdResult<bool> {
+    if OWNER.may_load(storage)?.as_ref() == Some(address) {
+        return Ok(true);
+    }
+    Ok(ROLES.has(storage, (role.as_str().to_string(), address.clone())))
+}
+
 #[cfg(test)]
 mod unit_tests {
     use super::*;
@@ -133,6 +711,7 @@ mod unit_tests {
     const INT_DATA_TYPE: &str = "int";
 
     const OWNER: &str = "owner";
+    const CALLBACK_LIMIT_GAS: u64 = 150000;
     
     fn add_commitments(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, commit_id: String, commit_time: u64, expired_time: u64) {
         let commitment: Commitment = Commitment {
@@ -147,9 +726,13 @@ mod unit_tests {
                 num: 32,
                 data_type: String::from(INT_DATA_TYPE),
             },
+            fee: Uint128::from(300u128),
+            delivery_mode: DeliveryMode::Callback,
+            callback_address: Addr::unchecked(OWNER),
+            callback_payload: None,
+            callback_gas_limit: CALLBACK_LIMIT_GAS,
         };
-        COMMITMENTS.push_front(&mut deps.storage, &commitment).unwrap();
-        PENDING_COMMITMENTS.save(&mut deps.storage, commit_id, &commitment).unwrap();
+        add_commitment(&mut deps.storage, &commitment).unwrap();
     }
 
     #[test]
@@ -159,11 +742,11 @@ mod unit_tests {
         add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
 
         let completion_time: Timestamp = Timestamp::from_seconds(4);
-        let commitments = get_commitments(&mut deps.storage, completion_time, 5u32).unwrap();
+        let commitments = get_commitments(&mut deps.storage, completion_time, completion_time, 5u32).unwrap();
 
         assert_eq!(commitments.len(), 1);
-        assert_eq!(COMMITMENTS.is_empty(&mut deps.storage).unwrap(), true);
-        assert_eq!(PENDING_COMMITMENTS.is_empty(&mut deps.storage), true);
+        assert!(COMMITMENTS.is_empty(&deps.storage).unwrap());
+        assert!(PENDING_COMMITMENTS.is_empty(&deps.storage));
     }
 
     #[test]
@@ -174,11 +757,11 @@ mod unit_tests {
         add_commitments(&mut deps, String::from("2"), 5u64, 10u64);
 
         let completion_time: Timestamp = Timestamp::from_seconds(6);
-        let commitments = get_commitments(&mut deps.storage, completion_time, 5u32).unwrap();
+        let commitments = get_commitments(&mut deps.storage, completion_time, completion_time, 5u32).unwrap();
 
         assert_eq!(commitments.len(), 1);
-        assert_eq!(COMMITMENTS.is_empty(&mut deps.storage).unwrap(), true);
-        assert_eq!(PENDING_COMMITMENTS.is_empty(&mut deps.storage), false);
+        assert!(COMMITMENTS.is_empty(&deps.storage).unwrap());
+        assert!(!PENDING_COMMITMENTS.is_empty(&deps.storage));
     }
 
     #[test]
@@ -193,11 +776,11 @@ mod unit_tests {
         add_commitments(&mut deps,String::from("6"),  0u64, 5u64);
 
         let completion_time: Timestamp = Timestamp::from_seconds(4);
-        let commitments = get_commitments(&mut deps.storage, completion_time, 5u32).unwrap();
+        let commitments = get_commitments(&mut deps.storage, completion_time, completion_time, 5u32).unwrap();
 
         assert_eq!(commitments.len(), 5);
-        assert_eq!(COMMITMENTS.len(&mut deps.storage).unwrap(), 1);
-        assert_eq!(PENDING_COMMITMENTS.is_empty(&mut deps.storage), false);
+        assert_eq!(COMMITMENTS.len(&deps.storage).unwrap(), 1);
+        assert!(!PENDING_COMMITMENTS.is_empty(&deps.storage));
     }
 
     #[test]
@@ -211,11 +794,11 @@ mod unit_tests {
         add_commitments(&mut deps, String::from("5"),  5u64, 10u64);
 
         let completion_time: Timestamp = Timestamp::from_seconds(4);
-        let commitments = get_commitments(&mut deps.storage, completion_time, 5u32).unwrap();
+        let commitments = get_commitments(&mut deps.storage, completion_time, completion_time, 5u32).unwrap();
 
         assert_eq!(commitments.len(), 4);
-        assert_eq!(COMMITMENTS.len(&mut deps.storage).unwrap(), 1);
-        assert_eq!(PENDING_COMMITMENTS.is_empty(&mut deps.storage), false);
+        assert_eq!(COMMITMENTS.len(&deps.storage).unwrap(), 1);
+        assert!(!PENDING_COMMITMENTS.is_empty(&deps.storage));
     }
 
 
@@ -236,15 +819,82 @@ mod unit_tests {
                 num: 32,
                 data_type: String::from(INT_DATA_TYPE),
             },
+            fee: Uint128::from(300u128),
+            delivery_mode: DeliveryMode::Callback,
+            callback_address: Addr::unchecked(OWNER),
+            callback_payload: None,
+            callback_gas_limit: CALLBACK_LIMIT_GAS,
         };
-        COMMITMENTS.push_back(&mut deps.storage, &commitment).unwrap();
-        PENDING_COMMITMENTS.save(&mut deps.storage, commit_id.clone(), &commitment).unwrap();
+        add_commitment(&mut deps.storage, &commitment).unwrap();
+
+        let get_commitment = get_commitment(&mut deps.storage, commit_id.clone(), Timestamp::from_seconds(4)).unwrap();
+
+        assert!(get_commitment.is_some());
+        assert!(!COMMITMENTS.is_empty(&deps.storage).unwrap());
+        assert!(PENDING_COMMITMENTS.is_empty(&deps.storage));
+        assert_eq!(COMMITMENT_STATUS.load(&deps.storage, commit_id).unwrap(), CommitmentStatus::Fulfilled {
+            source: RandomnessSource::Nois,
+            time: Timestamp::from_seconds(4),
+        });
+    }
+
+    #[test]
+    fn get_commitments_skip_commitment_fulfilled_by_nois() {
+        let mut deps = mock_dependencies();
+
+        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
+        add_commitments(&mut deps, String::from("2"), 0u64, 5u64);
+
+        // commitment "1" is delivered by nois before random org data arrives
+        let commitment = get_commitment(&mut deps.storage, String::from("1"), Timestamp::from_seconds(3)).unwrap();
+        assert!(commitment.is_some());
+
+        let completion_time: Timestamp = Timestamp::from_seconds(4);
+        let commitments = get_commitments(&mut deps.storage, completion_time, completion_time, 5u32).unwrap();
+
+        assert_eq!(commitments.len(), 1);
+        assert_eq!(commitments[0].id, String::from("2"));
+        assert!(COMMITMENTS.is_empty(&deps.storage).unwrap());
+        assert!(PENDING_COMMITMENTS.is_empty(&deps.storage));
+    }
+
+    #[test]
+    fn get_commitment_none_with_commitment_fulfilled_by_random_org() {
+        let mut deps = mock_dependencies();
+
+        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
+
+        let completion_time: Timestamp = Timestamp::from_seconds(4);
+        let commitments = get_commitments(&mut deps.storage, completion_time, completion_time, 5u32).unwrap();
+        assert_eq!(commitments.len(), 1);
+
+        let commitment = get_commitment(&mut deps.storage, String::from("1"), Timestamp::from_seconds(6)).unwrap();
+        assert!(commitment.is_none());
+        assert_eq!(COMMITMENT_STATUS.load(&deps.storage, String::from("1")).unwrap(), CommitmentStatus::Fulfilled {
+            source: RandomnessSource::RandomOrg,
+            time: completion_time,
+        });
+    }
+
+    #[test]
+    fn get_oldest_pending_commitment_pop_settled_commitments() {
+        let mut deps = mock_dependencies();
+
+        add_commitments(&mut deps, String::from("1"), 0u64, 5u64);
+        add_commitments(&mut deps, String::from("2"), 1u64, 6u64);
+        add_commitments(&mut deps, String::from("3"), 2u64, 7u64);
+
+        // commitments "1" and "2" are delivered by nois
+        get_commitment(&mut deps.storage, String::from("1"), Timestamp::from_seconds(3)).unwrap();
+        get_commitment(&mut deps.storage, String::from("2"), Timestamp::from_seconds(3)).unwrap();
 
-        let get_commitment = get_commitment(&mut deps.storage, commit_id).unwrap();
+        let oldest = get_oldest_pending_commitment(&mut deps.storage).unwrap();
+        assert_eq!(oldest.unwrap().id, String::from("3"));
+        assert_eq!(COMMITMENTS.len(&deps.storage).unwrap(), 1);
 
-        assert_eq!(get_commitment.is_some(), true);
-        assert_eq!(COMMITMENTS.is_empty(&mut deps.storage).unwrap(), false);
-        assert_eq!(PENDING_COMMITMENTS.is_empty(&mut deps.storage), true);
+        get_commitment(&mut deps.storage, String::from("3"), Timestamp::from_seconds(3)).unwrap();
+        assert!(get_oldest_pending_commitment(&mut deps.storage).unwrap().is_none());
+        assert!(COMMITMENTS.is_empty(&deps.storage).unwrap());
     }
 
 }
@@ -13,7 +13,7 @@ pub fn sha256_hash(string: &[u8]) -> Vec<u8> {
     // read hash digest and consume hasher
     let result = hasher.finalize();
 
-    return result.to_vec();
+    result.to_vec()
 }
 
 // calculate sha512 hash value
@@ -24,13 +24,13 @@ pub fn sha512_hash(string: &[u8]) -> Vec<u8> {
     // read hash digest and consume hasher
     let result = hasher.finalize();
 
-    return result.to_vec();
+    result.to_vec()
 }
 
 // generate commitment id from user 's address and user's nonce
 pub fn make_commit_id(address: String, nonce: u64) -> String{
     let seed = address + &nonce.to_string();
-    return hex::encode(sha256_hash(seed.as_bytes()));
+    hex::encode(sha256_hash(seed.as_bytes()))
 }
 
 // using nois's tool box to generate list of hex randomness
@@ -46,7 +46,7 @@ pub fn generate_hex_randomness(randomness: [u8;32], job_id: String, num: u32) ->
         return_data.push(hex::encode(sub_randomness));
     }
 
-    return return_data;
+    return_data
 }
 
 // using nois's tool box to generate list of integer randomness
@@ -65,7 +65,7 @@ pub fn generate_int_randomness(randomness: [u8;32], job_id: String, min: i32, ma
         return_data.push(int_randomness);
     }
 
-    return return_data;
+    return_data
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug,)]
@@ -101,14 +101,63 @@ pub fn decode_randomorg_data(data: String) -> Result<RandomOrgData, ContractErro
     // using serde_json_wasm, a serde-json alternative for CosmWasm smart contracts
     let random_org_data: RandomOrgData = serde_json_wasm::from_str(&data)
         .map_err(|_| ContractError::CustomError{val: String::from("Invalid random org data format!")})?;
-    return Ok(random_org_data);
+    Ok(random_org_data)
+}
+
+// only signed integer calls that draw 32 bytes in full 0..255 range are accepted, otherwise each "byte" can carry less entropy
+const RANDOM_ORG_METHOD: &str = "generateSignedIntegers";
+const RANDOM_ORG_N: u32 = 32;
+const RANDOM_ORG_MIN: u32 = 0;
+const RANDOM_ORG_MAX: u32 = 255;
+const RANDOM_ORG_BASE: u32 = 10;
+const DEVELOPER_LICENSE: &str = "developer";
+
+// check request parameters of random org data, developer license is rejected if `reject_developer_license` is set
+pub fn validate_randomorg_data(data: &RandomOrgData, reject_developer_license: bool) -> Result<(), ContractError> {
+    let invalid = |name: &str| Err(ContractError::InvalidRandomOrgParameter{name: name.to_string()});
+
+    if data.method != RANDOM_ORG_METHOD {
+        return invalid("method");
+    }
+    if data.n != RANDOM_ORG_N {
+        return invalid("n");
+    }
+    if data.min != RANDOM_ORG_MIN {
+        return invalid("min");
+    }
+    if data.max != RANDOM_ORG_MAX {
+        return invalid("max");
+    }
+    if !data.replacement {
+        return invalid("replacement");
+    }
+    if data.base != RANDOM_ORG_BASE {
+        return invalid("base");
+    }
+    if reject_developer_license && data.license.r#type == DEVELOPER_LICENSE {
+        return Err(ContractError::DeveloperLicenseRejected{});
+    }
+
+    Ok(())
 }
 
 // convert time with format "D:M:Y s:m:hZ" to Timestamp
 pub fn convert_datetime_string(data: String) -> Result<Timestamp, ContractError> {
     let date_time = data.parse::<DateTime<Local>>()
         .map_err(|_| ContractError::CustomError{val: String::from("Invalid date string format!")})?;
-    return Ok(Timestamp::from_nanos(date_time.timestamp_nanos() as u64));
+    Ok(Timestamp::from_nanos(date_time.timestamp_nanos() as u64))
+}
+
+// parse "major.minor.patch" version string, pre-release and build suffix of patch are ignored
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.splitn(3, '.');
+    let major = parts.next()?.parse::<u64>().ok()?;
+    let minor = parts.next()?.parse::<u64>().ok()?;
+    let patch = parts.next()?
+        .split(['-', '+'])
+        .next()?
+        .parse::<u64>().ok()?;
+    Some((major, minor, patch))
 }
 
 #[cfg(test)]
@@ -198,6 +247,62 @@ mod unit_tests {
         }
     }
 
+    fn valid_randomorg_data() -> RandomOrgData {
+        let message: String = String::from(r#"{"method":"generateSignedIntegers","hashedApiKey":"uSE6BGQ+JMXW38yyAf+/Q+YVZif1ix0RBgq4T2pry5PQhtnNLPWHJYBHdeS+uLkl7YPT/CqMPPJRci1jnd7zJw==","n":32,"min":0,"max":255,"replacement":true,"base":10,"pregeneratedRandomization":null,"data":[108,225,160,35,143,134,3,38,110,245,237,117,0,21,131,185,248,16,8,196,36,56,148,106,32,114,53,114,37,127,216,255],"license":{"type":"developer","text":"Random values licensed strictly for development and testing only","infoUrl":null},"licenseData":null,"userData":null,"ticketData":null,"completionTime":"2023-01-09 02:01:26Z","serialNumber":2}"#);
+        decode_randomorg_data(message).unwrap()
+    }
+
+    #[test]
+    fn validate_randomorg_data_success() {
+        validate_randomorg_data(&valid_randomorg_data(), false).unwrap();
+
+        let mut data = valid_randomorg_data();
+        data.license.r#type = "basic".to_string();
+        validate_randomorg_data(&data, true).unwrap();
+    }
+
+    fn assert_invalid_parameter(data: RandomOrgData, name: &str) {
+        match validate_randomorg_data(&data, false).unwrap_err() {
+            ContractError::InvalidRandomOrgParameter{name: v} => {assert_eq!(v, name)},
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn validate_randomorg_data_fail_with_invalid_parameter() {
+        let mut data = valid_randomorg_data();
+        data.method = "generateSignedDecimalFractions".to_string();
+        assert_invalid_parameter(data, "method");
+
+        let mut data = valid_randomorg_data();
+        data.n = 6;
+        assert_invalid_parameter(data, "n");
+
+        let mut data = valid_randomorg_data();
+        data.min = 1;
+        assert_invalid_parameter(data, "min");
+
+        let mut data = valid_randomorg_data();
+        data.max = 1;
+        assert_invalid_parameter(data, "max");
+
+        let mut data = valid_randomorg_data();
+        data.replacement = false;
+        assert_invalid_parameter(data, "replacement");
+
+        let mut data = valid_randomorg_data();
+        data.base = 16;
+        assert_invalid_parameter(data, "base");
+    }
+
+    #[test]
+    fn validate_randomorg_data_fail_with_developer_license() {
+        match validate_randomorg_data(&valid_randomorg_data(), true).unwrap_err() {
+            ContractError::DeveloperLicenseRejected{} => {},
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn convert_datetime_success() {
         let time: String = String::from(r#"2023-01-09 02:01:26Z"#);
@@ -213,4 +318,12 @@ mod unit_tests {
             _ => panic!(),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_version_success() {
+        assert_eq!(parse_version("0.1.0"), Some((0, 1, 0)));
+        assert_eq!(parse_version("1.12.3-beta.1"), Some((1, 12, 3)));
+        assert_eq!(parse_version("0.2"), None);
+        assert_eq!(parse_version("a.b.c"), None);
+    }
+}